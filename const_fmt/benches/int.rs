@@ -0,0 +1,55 @@
+use std::hint::black_box;
+
+use const_fmt::Buffer;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+// a mix of signs and digit counts, so neither the sign nor the length is predictable
+fn values() -> Vec<i64> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+
+    (0..1024)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state as i64) >> (state % 64)
+        })
+        .collect()
+}
+
+fn signed(c: &mut Criterion) {
+    let values = values();
+
+    c.bench_function("write_i64", |b| {
+        b.iter(|| {
+            for &value in &values {
+                let mut buffer = Buffer::new::<20>();
+                let _ = buffer.write_i64(black_box(value));
+                black_box(&buffer);
+            }
+        })
+    });
+
+    c.bench_function("write_i32", |b| {
+        b.iter(|| {
+            for &value in &values {
+                let mut buffer = Buffer::new::<11>();
+                let _ = buffer.write_i32(black_box(value as i32));
+                black_box(&buffer);
+            }
+        })
+    });
+
+    c.bench_function("write_i16", |b| {
+        b.iter(|| {
+            for &value in &values {
+                let mut buffer = Buffer::new::<6>();
+                let _ = buffer.write_i16(black_box(value as i16));
+                black_box(&buffer);
+            }
+        })
+    });
+}
+
+criterion_group!(benches, signed);
+criterion_main!(benches);
@@ -1,10 +1,45 @@
 #![allow(clippy::identity_op)]
 
-use core::{mem::MaybeUninit, num::NonZero};
+use core::{cmp::Ordering, mem::MaybeUninit, num::NonZero};
 
 use cfg_if::cfg_if;
 
-use crate::{ByteBuffer, Concat};
+use crate::macros::ConstFormat;
+use crate::{ByteBuffer, Concat, Concat3};
+
+#[cfg(feature = "alloc")]
+mod alloc;
+#[cfg(feature = "defmt")]
+mod defmt;
+mod encoding;
+mod escape;
+mod fixed;
+mod float;
+#[cfg(feature = "heapless")]
+mod heapless;
+mod hex;
+mod int;
+mod net;
+mod numeral;
+mod or_panic;
+mod pad;
+mod parse;
+mod text;
+#[cfg(feature = "ufmt")]
+mod ufmt;
+mod units;
+mod uuid;
+mod zeroize;
+
+pub use float::{RoundingMode, f64_fraction_digits, f64_integer_part};
+pub use hex::{ByteOrder, HEXDUMP_BYTES_PER_LINE};
+pub use int::{
+    WidthError, u8_digit_count, u16_digit_count, u32_digit_count, u64_digit_count,
+    u64_digit_count_radix, u128_digit_count, usize_digit_count,
+};
+pub use pad::{Align, FormatSpec, Sign};
+pub use parse::ParseError;
+pub use zeroize::Zeroizing;
 
 #[repr(C)]
 pub struct Buffer<B> {
@@ -12,15 +47,54 @@ pub struct Buffer<B> {
     buffer: MaybeUninit<B>,
 }
 
-macro_rules! write_uint {
-    ($ty:ident $writefun:ident) => {
+macro_rules! write_int {
+    ($ty:ident $writefun:ident $uty:ident $digits:ident) => {
+        /// Writes `value` in decimal, with a leading `-` if it is negative.
+        ///
+        /// The sign and digits are checked against the capacity together, so nothing is
+        /// written if they don't both fit.
         pub const fn $writefun(&mut self, value: $ty) -> Result<(), BufferWriteFailed> {
+            let sign = (value < 0) as usize;
+
+            if sign > self.remaining_capacity() {
+                return Err(BufferWriteFailed);
+            }
+
+            // the digits go after the room for the sign, which is only written once they fit,
+            // so there is just the one length computation and capacity check
+            let ptr = unsafe { self.as_mut_ptr().add(self.len) };
+            let remaining_capacity = self.remaining_capacity() - sign;
+            let magnitude = value.unsigned_abs() as $uty;
+            let len = tri!(unsafe { $digits(magnitude, remaining_capacity, ptr.add(sign)) });
+
+            if sign != 0 {
+                unsafe { ptr.write(b'-') };
+            }
+
+            self.len += sign + len;
+            Ok(())
+        }
+    };
+}
+
+macro_rules! push_digits {
+    ($ty:ident $fun:ident) => {
+        // writes the digits of `value` to `ptr` if there are at most `remaining_capacity` of
+        // them, and returns how many there are
+        //
+        // `ptr` must be valid for writes of `remaining_capacity` bytes
+        #[inline]
+        const unsafe fn $fun(
+            value: $ty,
+            remaining_capacity: usize,
+            ptr: *mut u8,
+        ) -> Result<usize, BufferWriteFailed> {
             // this imp function exists so that we don't duplicate this logic
             // on every instantiation of Buffer. Instead all instantiations
             // of Buffer will share this same implementation with some small
             // adjustments at the beginning and end
             // this will likely be inlined if there aren't many copies of it
-            const fn imp(
+            const unsafe fn imp(
                 value: NonZero<$ty>,
                 remaining_capacity: usize,
                 buffer_ptr: *mut u8,
@@ -33,7 +107,7 @@ macro_rules! write_uint {
                 }
 
                 let mut ptr = unsafe { buffer_ptr.add(len).cast::<[u8; 4]>() };
-                let total_len = len as usize;
+                let total_len = len;
 
                 while value >= 10000 {
                     let index = (value % 10000) as usize;
@@ -53,17 +127,67 @@ macro_rules! write_uint {
                 Ok(total_len)
             }
 
-            let Some(value) = NonZero::new(value) else {
-                return self.push_str("0");
-            };
+            // most values are small, so skip the loop and the ilog10 for them
+            if value < 10000 {
+                let value = value as u16;
+                let len =
+                    1 + (value >= 10) as usize + (value >= 100) as usize + (value >= 1000) as usize;
+
+                if len > remaining_capacity {
+                    return Err(BufferWriteFailed);
+                }
+
+                unsafe { write_lt_10000_unchecked(ptr, value, len) };
+                return Ok(len);
+            }
+
+            // value >= 10000 here
+            unsafe { imp(NonZero::new_unchecked(value), remaining_capacity, ptr) }
+        }
+    };
+}
+
+push_digits! { u16 push_u16_digits }
+push_digits! { u32 push_u32_digits }
+push_digits! { u64 push_u64_digits }
+push_digits! { u128 push_u128_digits }
+#[cfg(not(any(
+    target_pointer_width = "16",
+    target_pointer_width = "32",
+    target_pointer_width = "64"
+)))]
+push_digits! { usize push_usize_digits }
+
+macro_rules! write_uint {
+    ($ty:ident $writefun:ident $lenfun:ident $digits:ident) => {
+        pub const fn $writefun(&mut self, value: $ty) -> Result<(), BufferWriteFailed> {
+            match self.$lenfun(value) {
+                Ok(_) => Ok(()),
+                Err(err) => Err(err),
+            }
+        }
 
+        #[doc = concat!(
+                    "Like [`", stringify!($writefun), "`](Self::", stringify!($writefun), "), ",
+                    "but returns the number of digits written.",
+                )]
+        pub const fn $lenfun(&mut self, value: $ty) -> Result<usize, BufferWriteFailed> {
             let ptr = unsafe { self.as_mut_ptr().add(self.len) };
-            self.len += tri!(imp(value, self.remaining_capacity(), ptr));
-            Ok(())
+            let len = tri!(unsafe { $digits(value, self.remaining_capacity(), ptr) });
+            self.len += len;
+            Ok(len)
         }
     };
 }
 
+// a buffer only holds plain bytes, so it should be `Send` and `Sync` whenever its backing is
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Buffer<[u8; 16]>>();
+    assert_send_sync::<Buffer<Concat<[u8; 4], [u8; 8]>>>();
+};
+
 #[derive(Debug, Clone, Copy)]
 pub struct BufferWriteFailed;
 
@@ -71,10 +195,58 @@ impl Buffer<[u8; 0]> {
     pub const fn new<const N: usize>() -> Buffer<[u8; N]> {
         Buffer::create()
     }
+
+    /// Creates a buffer holding `s`, failing if it is longer than `N` bytes.
+    pub const fn from_str<const N: usize>(s: &str) -> Result<Buffer<[u8; N]>, BufferWriteFailed> {
+        let mut buffer = Buffer::new::<N>();
+        tri!(buffer.push_str(s));
+        Ok(buffer)
+    }
+
+    /// Creates a buffer holding all of `parts` one after the other, failing if together
+    /// they are longer than `N` bytes.
+    pub const fn concat_buffers_into<const N: usize>(
+        parts: &[&str],
+    ) -> Result<Buffer<[u8; N]>, BufferWriteFailed> {
+        let mut len: usize = 0;
+        let mut i = 0;
+
+        while i < parts.len() {
+            len = len.saturating_add(parts[i].len());
+            i += 1;
+        }
+
+        if len > N {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut buffer = Buffer::new::<N>();
+        let mut i = 0;
+
+        while i < parts.len() {
+            unsafe { buffer.push_str_unchecked(parts[i]) };
+            i += 1;
+        }
+
+        Ok(buffer)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for Buffer<[u8; N]> {
+    type Error = BufferWriteFailed;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Buffer::from_str(s)
+    }
 }
 
 impl<B: ByteBuffer> Buffer<B> {
-    const fn create() -> Self {
+    /// The number of bytes a buffer backed by `B` can hold, the same as
+    /// [`capacity`](Self::capacity).
+    pub const CAPACITY: usize = core::mem::size_of::<B>();
+
+    /// Creates an empty buffer backed by `B`.
+    pub const fn create() -> Self {
         Self {
             len: 0,
             buffer: MaybeUninit::uninit(),
@@ -85,6 +257,41 @@ impl<B: ByteBuffer> Buffer<B> {
         self.len = 0;
     }
 
+    /// Returns a mark for the current end of the written content, to go back to later with
+    /// [`reset_to`](Self::reset_to).
+    ///
+    /// ```
+    /// # use const_fmt::Buffer;
+    /// let mut buffer = Buffer::new::<16>();
+    /// buffer.push_str("[app] ").unwrap();
+    /// let header = buffer.checkpoint();
+    ///
+    /// buffer.push_str("started").unwrap();
+    /// assert_eq!(buffer.as_str(), "[app] started");
+    ///
+    /// buffer.reset_to(header);
+    /// buffer.push_str("ready").unwrap();
+    /// assert_eq!(buffer.as_str(), "[app] ready");
+    /// ```
+    pub const fn checkpoint(&self) -> usize {
+        self.len
+    }
+
+    /// Truncates the written content back to `mark`, usually from
+    /// [`checkpoint`](Self::checkpoint), keeping everything before it.
+    ///
+    /// # Panics
+    ///
+    /// If `mark` is past the end of the written content or not on a char boundary.
+    pub const fn reset_to(&mut self, mark: usize) {
+        assert!(
+            self.is_char_boundary(mark),
+            "`mark` is past the end or not a char boundary"
+        );
+
+        self.len = mark;
+    }
+
     pub const fn as_str(&self) -> &str {
         let ptr = self.as_ptr();
         let len = self.len();
@@ -92,8 +299,23 @@ impl<B: ByteBuffer> Buffer<B> {
         unsafe { core::str::from_utf8_unchecked(core::slice::from_raw_parts(ptr, len)) }
     }
 
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
+    /// Returns the written content mutably.
+    ///
+    /// The returned `str` always has exactly [`len`](Self::len) bytes, so it can be
+    /// changed in place but can't be used to shrink or grow the content.
+    pub const fn as_mut_str(&mut self) -> &mut str {
+        let len = self.len();
+        let ptr = self.as_mut_ptr();
+
+        unsafe { core::str::from_utf8_unchecked_mut(core::slice::from_raw_parts_mut(ptr, len)) }
+    }
+
     pub const fn capacity(&self) -> usize {
-        core::mem::size_of::<B>()
+        Self::CAPACITY
     }
 
     pub const fn len(&self) -> usize {
@@ -106,10 +328,39 @@ impl<B: ByteBuffer> Buffer<B> {
         self.len == 0
     }
 
+    /// Returns whether there is no room left for even a single byte.
+    pub const fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
     pub const fn remaining_capacity(&self) -> usize {
         unsafe { self.capacity().unchecked_sub(self.len) }
     }
 
+    /// Returns the unwritten part of the buffer, so bytes can be written into it directly
+    /// and then made part of the content with [`set_len`](Self::set_len).
+    pub const fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        let len = self.len;
+        let spare = self.remaining_capacity();
+
+        unsafe { core::slice::from_raw_parts_mut(self.as_mut_ptr().add(len).cast(), spare) }
+    }
+
+    /// Sets the length of the written content to `len`, like [`Vec::set_len`].
+    ///
+    /// # Safety
+    ///
+    /// * `len` must be at most [`capacity`](Self::capacity)
+    /// * the first `len` bytes must be initialized, for example through
+    ///   [`spare_capacity_mut`](Self::spare_capacity_mut)
+    /// * the first `len` bytes must be valid UTF-8, since [`as_str`](Self::as_str) doesn't
+    ///   check them again
+    ///
+    /// [`Vec::set_len`]: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.set_len
+    pub const unsafe fn set_len(&mut self, len: usize) {
+        self.len = len;
+    }
+
     const fn as_ptr(&self) -> *const u8 {
         (&raw const self.buffer).cast()
     }
@@ -118,6 +369,8 @@ impl<B: ByteBuffer> Buffer<B> {
         (&raw mut self.buffer).cast()
     }
 
+    // an empty `s` copies nothing, which is sound even into a zero sized or never written
+    // buffer: both pointers are non-null and bytes are always aligned
     const unsafe fn push_str_unchecked(&mut self, s: &str) {
         unsafe {
             self.as_mut_ptr()
@@ -127,6 +380,12 @@ impl<B: ByteBuffer> Buffer<B> {
         }
     }
 
+    // the byte must be ASCII and there must be room for it
+    const unsafe fn push_byte_unchecked(&mut self, byte: u8) {
+        unsafe { self.as_mut_ptr().add(self.len).write(byte) };
+        self.len += 1;
+    }
+
     pub const fn push_str(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
         if s.len() > self.remaining_capacity() {
             return Err(BufferWriteFailed);
@@ -153,6 +412,11 @@ impl<B: ByteBuffer> Buffer<B> {
             }
         }
 
+        // most characters are ASCII, which skips the encoding
+        if value.is_ascii() {
+            return self.write_ascii_char(value as u8);
+        }
+
         if value.len_utf8() > self.remaining_capacity() {
             return Err(BufferWriteFailed);
         }
@@ -167,7 +431,33 @@ impl<B: ByteBuffer> Buffer<B> {
         Ok(())
     }
 
+    /// Writes the ASCII character `c` as a single byte.
+    ///
+    /// # Panics
+    ///
+    /// If `c` isn't ASCII.
+    pub const fn write_ascii_char(&mut self, c: u8) -> Result<(), BufferWriteFailed> {
+        // anything else would leave invalid UTF-8 behind, so this is checked in release too
+        assert!(c.is_ascii(), "`write_ascii_char` only supports ASCII");
+
+        if self.is_full() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_byte_unchecked(c) };
+
+        Ok(())
+    }
+
     pub const fn write_u8(&mut self, value: u8) -> Result<(), BufferWriteFailed> {
+        match self.write_u8_len(value) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Like [`write_u8`](Self::write_u8), but returns the number of digits written.
+    pub const fn write_u8_len(&mut self, value: u8) -> Result<usize, BufferWriteFailed> {
         // u8_ilog10 is taken from Rust stdlib core::num::int_log10 module v1.86.0
         #[inline]
         pub const fn u8_ilog10(val: u8) -> u32 {
@@ -199,92 +489,362 @@ impl<B: ByteBuffer> Buffer<B> {
         self.len += len;
         unsafe { write_lt_10000_unchecked(ptr, value as u16, len) };
 
+        Ok(len)
+    }
+
+    /// Like [`write_u16`](Self::write_u16), but only for values below `10000`, which skips
+    /// the general digit loop.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is `10000` or more.
+    pub const fn write_u16_fast(&mut self, value: u16) -> Result<(), BufferWriteFailed> {
+        // the lookup is only indexed below 10000, so this has to be checked in release too
+        assert!(
+            value < 10000,
+            "`write_u16_fast` only supports values below 10000"
+        );
+
+        let len = 1 + (value >= 10) as usize + (value >= 100) as usize + (value >= 1000) as usize;
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let ptr = unsafe { self.as_mut_ptr().add(self.len) };
+        self.len += len;
+        unsafe { write_lt_10000_unchecked(ptr, value, len) };
+
         Ok(())
     }
 
-    write_uint! { u16 write_u16 }
-    write_uint! { u32 write_u32 }
-    write_uint! { u64 write_u64 }
-    write_uint! { u128 write_u128 }
+    write_uint! { u16 write_u16 write_u16_len push_u16_digits }
+    write_uint! { u32 write_u32 write_u32_len push_u32_digits }
+    write_uint! { u64 write_u64 write_u64_len push_u64_digits }
+    write_uint! { u128 write_u128 write_u128_len push_u128_digits }
 
     cfg_if! {
         if #[cfg(target_pointer_width = "16")] {
             pub const fn write_usize(&mut self, value: usize) -> Result<(), BufferWriteFailed> {
                 self.write_u16(value as _)
             }
+
+            /// Like [`write_usize`](Self::write_usize), but returns the number of digits
+            /// written.
+            pub const fn write_usize_len(
+                &mut self,
+                value: usize,
+            ) -> Result<usize, BufferWriteFailed> {
+                self.write_u16_len(value as _)
+            }
         } else if #[cfg(target_pointer_width = "32")] {
             pub const fn write_usize(&mut self, value: usize) -> Result<(), BufferWriteFailed> {
                 self.write_u32(value as _)
             }
+
+            /// Like [`write_usize`](Self::write_usize), but returns the number of digits
+            /// written.
+            pub const fn write_usize_len(
+                &mut self,
+                value: usize,
+            ) -> Result<usize, BufferWriteFailed> {
+                self.write_u32_len(value as _)
+            }
         } else if #[cfg(target_pointer_width = "64")] {
             pub const fn write_usize(&mut self, value: usize) -> Result<(), BufferWriteFailed> {
                 self.write_u64(value as _)
             }
+
+            /// Like [`write_usize`](Self::write_usize), but returns the number of digits
+            /// written.
+            pub const fn write_usize_len(
+                &mut self,
+                value: usize,
+            ) -> Result<usize, BufferWriteFailed> {
+                self.write_u64_len(value as _)
+            }
         } else {
-            write_uint! { usize write_usize }
+            write_uint! { usize write_usize write_usize_len push_usize_digits }
         }
     }
 
-    const fn push_neg(&mut self) -> Result<(), BufferWriteFailed> {
-        self.push_str("-")
+    /// Writes a `-` if `negative`, and nothing otherwise.
+    ///
+    /// Together with the unsigned writers this allows layouts like `-$42`.
+    pub const fn write_sign(&mut self, negative: bool) -> Result<(), BufferWriteFailed> {
+        if negative { self.push_str("-") } else { Ok(()) }
     }
 
-    pub const fn write_i8(&mut self, value: i8) -> Result<(), BufferWriteFailed> {
-        if value < 0 {
-            tri!(self.push_neg())
+    // the magnitude of an `i8` is at most 128, which the `u16` fast path covers
+    write_int! { i8 write_i8 u16 push_u16_digits }
+    write_int! { i16 write_i16 u16 push_u16_digits }
+    write_int! { i32 write_i32 u32 push_u32_digits }
+    write_int! { i64 write_i64 u64 push_u64_digits }
+    write_int! { i128 write_i128 u128 push_u128_digits }
+
+    /// Writes `value` in decimal, with a leading `-` if it is negative.
+    ///
+    /// The sign and digits are checked against the capacity together, so nothing is
+    /// written if they don't both fit.
+    pub const fn write_isize(&mut self, value: isize) -> Result<(), BufferWriteFailed> {
+        if isize::BITS <= i64::BITS {
+            self.write_i64(value as i64)
+        } else {
+            self.write_i128(value as i128)
         }
+    }
+
+    pub const fn append<A: ByteBuffer>(&self, other: &Buffer<A>) -> Buffer<Concat<B, A>> {
+        let mut out = Buffer::create();
+        unsafe { out.push_str_unchecked(self.as_str()) };
+        unsafe { out.push_str_unchecked(other.as_str()) };
+        out
+    }
 
-        self.write_u8(value.unsigned_abs())
+    /// Like [`append`](Self::append), but joins three buffers into one with room for all of
+    /// them, without nesting [`Concat`]s.
+    pub const fn append3<X: ByteBuffer, Y: ByteBuffer>(
+        &self,
+        b: &Buffer<X>,
+        c: &Buffer<Y>,
+    ) -> Buffer<Concat3<B, X, Y>> {
+        let mut out = Buffer::create();
+        unsafe { out.push_str_unchecked(self.as_str()) };
+        unsafe { out.push_str_unchecked(b.as_str()) };
+        unsafe { out.push_str_unchecked(c.as_str()) };
+        out
     }
 
-    pub const fn write_i16(&mut self, value: i16) -> Result<(), BufferWriteFailed> {
-        if value < 0 {
-            tri!(self.push_neg())
-        }
+    /// Like [`append`](Self::append), but appends `s` with room for up to `M` bytes of it.
+    ///
+    /// # Panics
+    ///
+    /// If `s` is longer than `M` bytes.
+    pub const fn append_str<const M: usize>(&self, s: &str) -> Buffer<Concat<B, [u8; M]>> {
+        assert!(
+            s.len() <= M,
+            "the string is longer than the capacity reserved for it"
+        );
+
+        let mut out = Buffer::create();
+        unsafe { out.push_str_unchecked(self.as_str()) };
+        unsafe { out.push_str_unchecked(s) };
+        out
+    }
 
-        self.write_u16(value.unsigned_abs())
+    /// Concatenates `self` and `other` into a buffer backed by a `[u8; N]`, failing if the
+    /// combined content is longer than `N` bytes.
+    pub const fn try_append_into<const N: usize, A: ByteBuffer>(
+        &self,
+        other: &Buffer<A>,
+    ) -> Result<Buffer<[u8; N]>, BufferWriteFailed> {
+        let mut out = Buffer::new::<N>();
+        tri!(out.push_str(self.as_str()));
+        tri!(out.push_str(other.as_str()));
+        Ok(out)
     }
 
-    pub const fn write_i32(&mut self, value: i32) -> Result<(), BufferWriteFailed> {
-        if value < 0 {
-            tri!(self.push_neg())
-        }
+    /// Moves the contents into a buffer backed by a single `[u8; N]`, collapsing nested
+    /// [`Concat`] types. `N` must equal [`CAPACITY`](Self::CAPACITY), and is usually inferred.
+    ///
+    /// ```
+    /// # use const_fmt::Buffer;
+    /// let name = Buffer::new::<8>().append_str::<4>("name");
+    /// let flat: Buffer<[u8; 12]> = name.flatten();
+    /// assert_eq!(flat.as_str(), "name");
+    /// ```
+    ///
+    /// A mismatched `N` fails to compile.
+    ///
+    /// ```compile_fail
+    /// # use const_fmt::Buffer;
+    /// let flat: Buffer<[u8; 11]> = Buffer::new::<12>().flatten();
+    /// ```
+    pub const fn flatten<const N: usize>(self) -> Buffer<[u8; N]> {
+        const { assert!(N == Self::CAPACITY, "N must be the capacity of the buffer") };
+
+        let mut out = Buffer::create();
+        unsafe { out.push_str_unchecked(self.as_str()) };
+        out
+    }
 
-        self.write_u32(value.unsigned_abs())
+    /// Copies the contents into a new buffer with a capacity of `N`, which may be smaller
+    /// than this one's. Fails if the contents don't fit.
+    pub const fn shrink_to<const N: usize>(&self) -> Result<Buffer<[u8; N]>, BufferWriteFailed> {
+        let mut out = Buffer::new::<N>();
+        tri!(out.push_str(self.as_str()));
+        Ok(out)
     }
+}
 
-    pub const fn write_i64(&mut self, value: i64) -> Result<(), BufferWriteFailed> {
-        if value < 0 {
-            tri!(self.push_neg())
-        }
+// only the written bytes are copied, the rest of the new buffer stays uninitialized
+impl<B: ByteBuffer> Clone for Buffer<B> {
+    fn clone(&self) -> Self {
+        let mut out = Self::create();
+        unsafe { out.push_str_unchecked(self.as_str()) };
+        out
+    }
+}
 
-        self.write_u64(value.unsigned_abs())
+// copying a `MaybeUninit<B>` is fine even if parts of it are uninitialized
+impl<B: ByteBuffer + Copy> Copy for Buffer<B> {}
+
+impl<B: ByteBuffer> core::fmt::Display for Buffer<B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
     }
+}
 
-    pub const fn write_i128(&mut self, value: i128) -> Result<(), BufferWriteFailed> {
-        if value < 0 {
-            tri!(self.push_neg())
-        }
+impl<B: ByteBuffer> core::fmt::Write for Buffer<B> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s)
+            .map_err(|BufferWriteFailed| core::fmt::Error)
+    }
+}
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes already captured formatting arguments, like those from `format_args!`.
+    ///
+    /// Fails without writing anything if the whole output doesn't fit.
+    pub fn write_args(&mut self, args: core::fmt::Arguments<'_>) -> Result<(), BufferWriteFailed> {
+        let len = self.len;
 
-        self.write_u128(value.unsigned_abs())
+        core::fmt::Write::write_fmt(self, args).map_err(|core::fmt::Error| {
+            self.len = len;
+            BufferWriteFailed
+        })
     }
 
-    pub const fn write_isize(&mut self, value: isize) -> Result<(), BufferWriteFailed> {
-        if value < 0 {
-            tri!(self.push_neg())
+    /// Writes `arr` like `Debug` would, as `[1, 2, 3]`.
+    ///
+    /// Fails without writing anything if the whole output doesn't fit.
+    pub fn write_array<T: ConstFormat, const N: usize>(
+        &mut self,
+        arr: &[T; N],
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_list(arr, "[", ", ", "]")
+    }
+
+    /// Runs `write` on the buffer and returns how many bytes it added.
+    ///
+    /// If `write` fails, everything it wrote is removed again.
+    pub fn measured(
+        &mut self,
+        write: impl FnOnce(&mut Self) -> Result<(), BufferWriteFailed>,
+    ) -> Result<usize, BufferWriteFailed> {
+        let len = self.len;
+
+        match write(self) {
+            Ok(()) => Ok(self.len - len),
+            Err(err) => {
+                self.len = len;
+                Err(err)
+            }
+        }
+    }
+
+    /// Writes `items` between `open` and `close`, with `sep` between each pair of items,
+    /// so `write_list(&[1, 2, 3], "(", "; ", ")")` writes `(1; 2; 3)`.
+    ///
+    /// Fails without writing anything if the whole output doesn't fit.
+    pub fn write_list<T: ConstFormat>(
+        &mut self,
+        items: &[T],
+        open: &str,
+        sep: &str,
+        close: &str,
+    ) -> Result<(), BufferWriteFailed> {
+        let len = self.len;
+        let mut write = || {
+            tri!(self.push_str(open));
+
+            let mut i = 0;
+
+            while i < items.len() {
+                if i != 0 {
+                    tri!(self.push_str(sep));
+                }
+
+                tri!(items[i].write_to(self));
+                i += 1;
+            }
+
+            self.push_str(close)
+        };
+
+        let result = write();
+
+        if result.is_err() {
+            self.len = len;
         }
 
-        self.write_usize(value.unsigned_abs())
+        result
     }
+}
 
-    pub const fn append<A: ByteBuffer>(&self, other: &Buffer<A>) -> Buffer<Concat<B, A>> {
-        let mut out = Buffer::create();
-        unsafe { out.push_str_unchecked(self.as_str()) };
-        unsafe { out.push_str_unchecked(other.as_str()) };
-        out
+// comparisons only look at the written content, so buffers of different capacities can be compared
+impl<A: ByteBuffer, B: ByteBuffer> PartialEq<Buffer<A>> for Buffer<B> {
+    fn eq(&self, other: &Buffer<A>) -> bool {
+        self.as_str() == other.as_str()
     }
 }
 
+impl<B: ByteBuffer> Eq for Buffer<B> {}
+
+impl<B: ByteBuffer> PartialEq<str> for Buffer<B> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<B: ByteBuffer> PartialEq<&str> for Buffer<B> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<B: ByteBuffer> PartialEq<[u8]> for Buffer<B> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
+impl<B: ByteBuffer> PartialEq<&[u8]> for Buffer<B> {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.as_bytes() == *other
+    }
+}
+
+impl<A: ByteBuffer, B: ByteBuffer> PartialOrd<Buffer<A>> for Buffer<B> {
+    fn partial_cmp(&self, other: &Buffer<A>) -> Option<Ordering> {
+        Some(self.as_str().cmp(other.as_str()))
+    }
+}
+
+impl<B: ByteBuffer> Ord for Buffer<B> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+// indexes the written content like a `str`, with the same panics
+impl<B: ByteBuffer, I: core::slice::SliceIndex<str>> core::ops::Index<I> for Buffer<B> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &I::Output {
+        &self.as_str()[index]
+    }
+}
+
+/// Joins the contents of `a` and `b` into a buffer with room for both, the same as
+/// [`a.append(b)`](Buffer::append).
+pub const fn join<A: ByteBuffer, B: ByteBuffer>(
+    a: &Buffer<A>,
+    b: &Buffer<B>,
+) -> Buffer<Concat<A, B>> {
+    a.append(b)
+}
+
 const unsafe fn write_lt_10000_unchecked(ptr: *mut u8, value: u16, len: usize) {
     unsafe {
         // point to the current end of the buffer
@@ -327,6 +887,76 @@ static LOOKUP_10000: [u8; 40000] = {
     lookup
 };
 
+#[test]
+fn test_spare_capacity() {
+    const FILLED: Buffer<[u8; 8]> = {
+        let mut buffer = Buffer::new::<8>();
+        buffer.push_str_or_panic("ab");
+
+        let spare = buffer.spare_capacity_mut();
+        assert!(spare.len() == 6);
+        spare[0] = MaybeUninit::new(b'c');
+        spare[1] = MaybeUninit::new(b'd');
+        unsafe { buffer.set_len(4) };
+
+        buffer
+    };
+
+    assert_eq!(FILLED.as_str(), "abcd");
+
+    let mut buffer = FILLED;
+    unsafe { buffer.set_len(1) };
+    assert_eq!(buffer.as_str(), "a");
+    assert_eq!(buffer.spare_capacity_mut().len(), 7);
+
+    buffer.push_str("1234567").unwrap();
+    assert!(buffer.spare_capacity_mut().is_empty());
+}
+
+#[test]
+fn test_ascii_char() {
+    let mut buffer = Buffer::new::<3>();
+    buffer.write_ascii_char(b'a').unwrap();
+    buffer.write_char('\n').unwrap();
+    buffer.write_ascii_char(0x7f).unwrap();
+    assert!(buffer.write_ascii_char(b'b').is_err());
+    assert!(buffer.write_char('c').is_err());
+    assert_eq!(buffer.as_str(), "a\n\x7f");
+}
+
+#[test]
+#[should_panic = "only supports ASCII"]
+fn test_ascii_char_non_ascii() {
+    let _ = Buffer::new::<8>().write_ascii_char(0xc3);
+}
+
+#[test]
+fn test_u16_fast() {
+    use std::fmt::Write;
+
+    let mut buffer = Buffer::new::<4>();
+    let mut expected = String::new();
+
+    for i in 0..10000 {
+        buffer.clear();
+        expected.clear();
+
+        buffer.write_u16_fast(i).unwrap();
+        write!(expected, "{i}").unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_u16_fast(1000).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic = "only supports values below 10000"]
+fn test_u16_fast_out_of_range() {
+    Buffer::new::<8>().write_u16_fast(10000).unwrap();
+}
+
 #[test]
 fn test_all_u8() {
     use std::fmt::Write;
@@ -370,6 +1000,589 @@ fn test_all_u32() {
     }
 }
 
+#[test]
+fn test_all_i8() {
+    use std::fmt::Write;
+
+    let mut s = String::new();
+    for i in i8::MIN..=i8::MAX {
+        let mut buffer = Buffer::<[u8; 4]>::create();
+        let _ = buffer.write_i8(i);
+        s.clear();
+        let _ = write!(s, "{i}");
+        assert_eq!(buffer.as_str(), s);
+    }
+}
+
+#[test]
+fn test_all_i16() {
+    use std::fmt::Write;
+
+    let mut s = String::new();
+    for i in i16::MIN..=i16::MAX {
+        let mut buffer = Buffer::<[u8; 6]>::create();
+        let _ = buffer.write_i16(i);
+        s.clear();
+        let _ = write!(s, "{i}");
+        assert_eq!(buffer.as_str(), s);
+
+        // one byte short, so either the sign or the last digit doesn't fit
+        let mut buffer = Buffer::new::<6>();
+        buffer.push_str(&"x".repeat(7 - s.len())).unwrap();
+        assert!(buffer.write_i16(i).is_err());
+        assert_eq!(buffer.len(), 7 - s.len());
+    }
+}
+
+#[test]
+#[ignore = "slow"]
+fn test_all_i32() {
+    use std::fmt::Write;
+
+    let mut s = String::new();
+    for i in i32::MIN..=i32::MAX {
+        let mut buffer = Buffer::<[u8; 11]>::create();
+        let _ = buffer.write_i32(i);
+        s.clear();
+        let _ = write!(s, "{i}");
+        assert_eq!(buffer.as_str(), s);
+    }
+}
+
+#[test]
+fn test_small_i64() {
+    for i in (-10001..=10001).chain([-99_999, -100_000, i64::MIN, i64::MAX]) {
+        let expected = i.to_string();
+
+        let mut buffer = Buffer::<[u8; 20]>::create();
+        buffer.write_i64(i).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+
+        let mut buffer = Buffer::<[u8; 40]>::create();
+        buffer.write_i128(i as i128).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+
+        if let Ok(i) = isize::try_from(i) {
+            let mut buffer = Buffer::<[u8; 20]>::create();
+            buffer.write_isize(i).unwrap();
+            assert_eq!(buffer.as_str(), expected);
+        }
+    }
+
+    let mut buffer = Buffer::new::<40>();
+    buffer.write_i128(i128::MIN).unwrap();
+    assert_eq!(buffer.as_str(), i128::MIN.to_string());
+}
+
+#[test]
+fn test_small_u64() {
+    for i in (0..=10000).chain([99_999, 100_000, u64::MAX]) {
+        let expected = i.to_string();
+
+        let mut buffer = Buffer::<[u8; 20]>::create();
+        buffer.write_u64(i).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+
+        let mut buffer = Buffer::<[u8; 20]>::create();
+        buffer.write_u128(i as u128).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+
+        let mut buffer = Buffer::new::<20>();
+        buffer.push_str(&"x".repeat(21 - expected.len())).unwrap();
+        assert!(buffer.write_u64(i).is_err());
+    }
+}
+
+#[test]
+fn test_from_str() {
+    const GREETING: Buffer<[u8; 8]> = match Buffer::from_str("hello") {
+        Ok(buffer) => buffer,
+        Err(_) => panic!(),
+    };
+    assert_eq!(GREETING.as_str(), "hello");
+    assert!(Buffer::from_str::<4>("hello").is_err());
+
+    let buffer: Buffer<[u8; 16]> = "hi".try_into().unwrap();
+    assert_eq!(buffer.as_str(), "hi");
+    assert!(Buffer::<[u8; 1]>::try_from("hi").is_err());
+}
+
+#[test]
+fn test_concat_buffers_into() {
+    let mut name = Buffer::new::<8>();
+    name.push_str("world").unwrap();
+
+    let joined = Buffer::concat_buffers_into::<12>(&["hello", ", ", name.as_str()]).unwrap();
+    assert_eq!(joined.as_str(), "hello, world");
+
+    assert!(Buffer::concat_buffers_into::<0>(&[]).unwrap().is_empty());
+    assert!(Buffer::concat_buffers_into::<11>(&["hello", ", ", name.as_str()]).is_err());
+
+    const PATH: Buffer<[u8; 16]> = match Buffer::concat_buffers_into(&["/usr", "/lib"]) {
+        Ok(buffer) => buffer,
+        Err(_) => panic!(),
+    };
+    assert_eq!(PATH.as_str(), "/usr/lib");
+}
+
+#[test]
+fn test_is_full() {
+    let mut buffer = Buffer::new::<3>();
+
+    while !buffer.is_full() {
+        buffer.push_str("a").unwrap();
+    }
+
+    assert_eq!(buffer.as_str(), "aaa");
+    assert!(Buffer::new::<0>().is_full());
+    assert!(!Buffer::new::<1>().is_full());
+}
+
+#[test]
+fn test_try_append_into() {
+    let mut a = Buffer::new::<8>();
+    a.push_str("abc").unwrap();
+    let mut b = Buffer::new::<8>();
+    b.push_str("defg").unwrap();
+
+    let joined = a.try_append_into::<7, _>(&b).unwrap();
+    assert_eq!(joined.as_str(), "abcdefg");
+    assert_eq!(joined.capacity(), 7);
+
+    assert!(a.try_append_into::<6, _>(&b).is_err());
+    assert!(b.try_append_into::<2, _>(&a).is_err());
+}
+
+#[test]
+fn test_flatten() {
+    let mut key = Buffer::new::<4>();
+    key.push_str("key").unwrap();
+    let concat = key.append_str::<2>("=").append_str::<8>("value");
+    let mut flat: Buffer<[u8; 14]> = concat.flatten();
+    assert_eq!(flat.as_str(), "key=value");
+    assert_eq!(flat.remaining_capacity(), 5);
+    flat.push_str("!!!!!").unwrap();
+    assert_eq!(flat.as_str(), "key=value!!!!!");
+}
+
+#[test]
+fn test_write_array() {
+    let mut buffer = Buffer::new::<32>();
+    buffer.write_array(&[1u8, 2, 3]).unwrap();
+    assert_eq!(buffer.as_str(), "[1, 2, 3]");
+
+    buffer.clear();
+    buffer.write_array::<i32, 0>(&[]).unwrap();
+    assert_eq!(buffer.as_str(), "[]");
+
+    buffer.clear();
+    buffer.write_array(&[&"a", &"bc"]).unwrap();
+    assert_eq!(buffer.as_str(), "[a, bc]");
+
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("x").unwrap();
+    assert!(buffer.write_array(&[-10i64, 20]).is_err());
+    assert_eq!(buffer.as_str(), "x");
+}
+
+#[test]
+fn test_shrink_to() {
+    const SHRUNK: Buffer<[u8; 5]> = {
+        let mut buffer = Buffer::new::<64>();
+        buffer.push_str_or_panic("hello");
+        match buffer.shrink_to::<5>() {
+            Ok(buffer) => buffer,
+            Err(_) => panic!(),
+        }
+    };
+    assert_eq!(SHRUNK.as_str(), "hello");
+    assert!(SHRUNK.is_full());
+
+    let buffer = Buffer::new::<8>().append_str::<8>("abc");
+    assert_eq!(buffer.shrink_to::<16>().unwrap().as_str(), "abc");
+    assert!(buffer.shrink_to::<2>().is_err());
+    assert!(Buffer::new::<4>().shrink_to::<0>().unwrap().is_empty());
+}
+
+#[test]
+fn test_write_list() {
+    let mut buffer = Buffer::new::<32>();
+    buffer.write_list(&[1u8, 2, 3], "(", "; ", ")").unwrap();
+    assert_eq!(buffer.as_str(), "(1; 2; 3)");
+
+    buffer.clear();
+    buffer.write_list(&["a", "b"], "{", ", ", "}").unwrap();
+    assert_eq!(buffer.as_str(), "{a, b}");
+
+    buffer.clear();
+    buffer.write_list::<u8>(&[], "<", ",", ">").unwrap();
+    assert_eq!(buffer.as_str(), "<>");
+
+    buffer.clear();
+    buffer.write_list(&['x', 'y'], "", "", "").unwrap();
+    assert_eq!(buffer.as_str(), "xy");
+
+    // fails on the closing bracket, after everything else was written
+    let mut buffer = Buffer::new::<6>();
+    buffer.push_str("x").unwrap();
+    assert!(buffer.write_list(&[12u8, 34], "", "-", "!").is_err());
+    assert_eq!(buffer.as_str(), "x");
+}
+
+#[test]
+fn test_write_len() {
+    let mut buffer = Buffer::new::<64>();
+    assert_eq!(buffer.write_u8_len(7).unwrap(), 1);
+    assert_eq!(buffer.write_u16_len(255).unwrap(), 3);
+    assert_eq!(buffer.write_u32_len(123_456).unwrap(), 6);
+    assert_eq!(buffer.write_u64_len(u64::MAX).unwrap(), 20);
+    assert_eq!(buffer.write_u128_len(0).unwrap(), 1);
+    assert_eq!(buffer.write_usize_len(10).unwrap(), 2);
+    assert_eq!(buffer.len(), 33);
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_u32_len(12_345).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_sign() {
+    let mut buffer = Buffer::new::<4>();
+    let value = -42i32;
+    buffer.write_sign(value < 0).unwrap();
+    buffer.push_str("$").unwrap();
+    buffer.write_u32(value.unsigned_abs()).unwrap();
+    assert_eq!(buffer.as_str(), "-$42");
+
+    buffer.write_sign(false).unwrap();
+    assert!(buffer.write_sign(true).is_err());
+    assert_eq!(buffer.as_str(), "-$42");
+}
+
+#[test]
+fn test_signed_atomic() {
+    // the digits fit on their own, but not together with the sign
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_i8(-128).is_err());
+    assert!(buffer.write_i16(-128).is_err());
+    assert!(buffer.write_i32(-128).is_err());
+    assert!(buffer.write_i64(-128).is_err());
+    assert!(buffer.write_i128(-128).is_err());
+    assert!(buffer.write_isize(-128).is_err());
+    assert!(buffer.is_empty());
+
+    buffer.write_i8(127).unwrap();
+    assert_eq!(buffer.as_str(), "127");
+
+    let mut buffer = Buffer::new::<40>();
+    buffer.write_i128(i128::MIN).unwrap();
+    assert_eq!(buffer.as_str(), i128::MIN.to_string());
+
+    let mut buffer = Buffer::new::<19>();
+    assert!(buffer.write_i64(i64::MIN).is_err());
+    assert!(buffer.is_empty());
+    buffer.write_i64(i64::MAX).unwrap();
+    assert_eq!(buffer.as_str(), i64::MAX.to_string());
+}
+
+#[test]
+fn test_eq_str_and_bytes() {
+    let mut buffer = Buffer::new::<8>();
+    buffer.write_hex_color([0xab, 0xcd, 0xef]).unwrap();
+
+    assert_eq!(buffer.as_bytes(), b"#abcdef");
+    assert!(buffer == "#abcdef");
+    assert!(buffer == *"#abcdef");
+    assert!(buffer == b"#abcdef"[..]);
+    assert!(buffer != "#abcde");
+
+    let fixture: &[u8] = b"#abcdef";
+    assert!(buffer == fixture);
+    let fixture: &[u8] = b"#ABCDEF";
+    assert!(buffer != fixture);
+}
+
+#[test]
+fn test_capacity_const() {
+    const N: usize = Buffer::<[u8; 24]>::CAPACITY;
+    let scratch = [0u8; N];
+
+    assert_eq!(scratch.len(), 24);
+    assert_eq!(Buffer::<Concat<[u8; 3], [u8; 5]>>::CAPACITY, 8);
+    assert_eq!(Buffer::new::<7>().capacity(), Buffer::<[u8; 7]>::CAPACITY);
+}
+
+#[test]
+fn test_write_args() {
+    let mut buffer = Buffer::new::<12>();
+    buffer
+        .write_args(format_args!("{}-{:03}", "ab", 7))
+        .unwrap();
+    assert_eq!(buffer.as_str(), "ab-007");
+
+    assert!(buffer.write_args(format_args!("{:?}", "quoted")).is_err());
+    assert_eq!(buffer.as_str(), "ab-007");
+
+    core::fmt::Write::write_char(&mut buffer, '!').unwrap();
+    assert_eq!(buffer.as_str(), "ab-007!");
+}
+
+#[test]
+fn test_measured() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.push_str("x = ").unwrap();
+
+    let len = buffer
+        .measured(|buffer| {
+            buffer.write_char('é')?;
+            buffer.write_u32(1234)
+        })
+        .unwrap();
+    assert_eq!(len, 6);
+    assert_eq!(buffer.measured(|_| Ok(())).unwrap(), 0);
+
+    // a failed write is undone, even the parts that fit
+    let result = buffer.measured(|buffer| {
+        buffer.push_str("abc")?;
+        buffer.push_str("too long")
+    });
+    assert!(result.is_err());
+    assert_eq!(buffer.as_str(), "x = é1234");
+}
+
+#[test]
+fn test_append_str() {
+    const GREETING: Buffer<Concat<[u8; 8], [u8; 6]>> = {
+        let mut name = Buffer::new::<8>();
+        let _ = name.push_str("hello");
+        name.append_str::<6>(", you")
+    };
+
+    assert_eq!(GREETING.as_str(), "hello, you");
+    assert_eq!(GREETING.capacity(), 14);
+}
+
+#[test]
+fn test_reset_to() {
+    let mut buffer = Buffer::new::<16>();
+    assert_eq!(buffer.checkpoint(), 0);
+    buffer.push_str("é: ").unwrap();
+    let header = buffer.checkpoint();
+    assert_eq!(header, 4);
+
+    for value in [1, 22, 333] {
+        buffer.reset_to(header);
+        buffer.write_u32(value).unwrap();
+        assert_eq!(buffer.as_str(), format!("é: {value}"));
+    }
+
+    buffer.reset_to(buffer.len());
+    assert_eq!(buffer.as_str(), "é: 333");
+    buffer.reset_to(0);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic = "not a char boundary"]
+fn test_reset_to_inside_char() {
+    let mut buffer = Buffer::new::<4>();
+    buffer.push_str("é").unwrap();
+    buffer.reset_to(1);
+}
+
+#[test]
+#[should_panic = "past the end"]
+fn test_reset_to_past_end() {
+    let mut buffer = Buffer::new::<4>();
+    buffer.push_str("ab").unwrap();
+    buffer.reset_to(3);
+}
+
+#[test]
+fn test_index() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.push_str("key=vé").unwrap();
+
+    assert_eq!(&buffer[..3], "key");
+    assert_eq!(&buffer[4..], "vé");
+    assert_eq!(&buffer[2..5], "y=v");
+    assert_eq!(&buffer[..=3], "key=");
+    assert_eq!(&buffer[..], "key=vé");
+    assert_eq!(&buffer[7..], "");
+}
+
+#[test]
+#[should_panic = "is not a char boundary"]
+fn test_index_inside_char() {
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("vé").unwrap();
+    let _ = &buffer[..2];
+}
+
+#[test]
+#[should_panic = "out of bounds"]
+fn test_index_past_end() {
+    // the spare capacity can't be indexed
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("ab").unwrap();
+    let _ = &buffer[1..4];
+}
+
+#[test]
+fn test_append_empty() {
+    // never written to, so the backing bytes are all uninitialized
+    let empty = Buffer::new::<0>();
+    let unwritten = Buffer::new::<4>();
+    let mut written = Buffer::new::<4>();
+    written.push_str("ab").unwrap();
+
+    let both = empty.append(&Buffer::new::<0>());
+    assert!(both.is_empty());
+    assert_eq!(both.capacity(), 0);
+    assert_eq!(both.as_str(), "");
+
+    let out = empty.append(&unwritten);
+    assert!(out.is_empty());
+    assert_eq!(out.capacity(), 4);
+
+    let out = unwritten.append(&empty);
+    assert!(out.is_empty());
+    assert_eq!(out.capacity(), 4);
+
+    assert_eq!(empty.append(&written).as_str(), "ab");
+    assert_eq!(written.append(&empty).as_str(), "ab");
+    assert_eq!(unwritten.append(&written).as_str(), "ab");
+
+    let mut out = written.append(&unwritten);
+    assert_eq!(out.as_str(), "ab");
+    out.push_str("cdefgh").unwrap();
+    assert_eq!(out.as_str(), "abcdefgh");
+    assert!(out.is_full());
+
+    assert!(empty.append_str::<0>("").is_empty());
+    assert!(empty.append3(&empty, &unwritten).is_empty());
+}
+
+#[test]
+fn test_append3() {
+    const PATH: Buffer<Concat3<[u8; 4], [u8; 2], [u8; 8]>> = {
+        let mut dir = Buffer::new::<4>();
+        dir.push_str_or_panic("/usr");
+        let mut sep = Buffer::new::<2>();
+        sep.push_str_or_panic("/");
+        let mut file = Buffer::new::<8>();
+        file.push_str_or_panic("bin");
+        dir.append3(&sep, &file)
+    };
+
+    assert_eq!(PATH.as_str(), "/usr/bin");
+    assert_eq!(PATH.capacity(), 14);
+
+    let mut copy = PATH;
+    copy.push_str("/ls").unwrap();
+    assert_eq!(copy.as_str(), "/usr/bin/ls");
+}
+
+#[test]
+fn test_join() {
+    const JOINED: Buffer<Concat<[u8; 4], [u8; 8]>> = {
+        let mut a = Buffer::new::<4>();
+        a.push_str_or_panic("key=");
+        let mut b = Buffer::new::<8>();
+        b.push_str_or_panic("value");
+        join(&a, &b)
+    };
+
+    assert_eq!(JOINED.as_str(), "key=value");
+    assert_eq!(JOINED.capacity(), 12);
+
+    let joined = join(&Buffer::new::<0>(), &Buffer::new::<2>());
+    assert!(joined.is_empty());
+}
+
+#[test]
+#[should_panic = "longer than the capacity"]
+fn test_append_str_too_long() {
+    Buffer::new::<8>().append_str::<2>("abc");
+}
+
+#[test]
+fn test_as_mut_str() {
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("abc").unwrap();
+
+    let s = buffer.as_mut_str();
+    assert_eq!(s.len(), 3);
+    s.make_ascii_uppercase();
+
+    assert_eq!(buffer.as_str(), "ABC");
+}
+
+#[test]
+fn test_clone() {
+    #[repr(C)]
+    struct NotCopy([u8; 8]);
+    unsafe impl ByteBuffer for NotCopy {}
+
+    let mut buffer = Buffer::<NotCopy>::create();
+    buffer.push_str("abc").unwrap();
+
+    let mut snapshot = buffer.clone();
+    buffer.push_str("def").unwrap();
+    snapshot.push_str("!").unwrap();
+    assert_eq!(buffer.as_str(), "abcdef");
+    assert_eq!(snapshot.as_str(), "abc!");
+
+    const COPIED: (Buffer<[u8; 4]>, Buffer<[u8; 4]>) = {
+        let mut buffer = Buffer::new::<4>();
+        let _ = buffer.push_str("ab");
+        let snapshot = buffer;
+        let _ = buffer.push_str("cd");
+        (buffer, snapshot)
+    };
+    assert_eq!(COPIED.0.as_str(), "abcd");
+    assert_eq!(COPIED.1.as_str(), "ab");
+
+    let joined = COPIED.1.append(&COPIED.0);
+    let copy = joined;
+    assert_eq!(joined.as_str(), copy.as_str());
+}
+
+#[test]
+fn test_in_static_mutex() {
+    static BUFFER: std::sync::Mutex<Buffer<[u8; 16]>> = std::sync::Mutex::new(Buffer::new::<16>());
+
+    std::thread::spawn(|| BUFFER.lock().unwrap().push_str("from thread"))
+        .join()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(BUFFER.lock().unwrap().as_str(), "from thread");
+}
+
+#[test]
+fn test_cmp() {
+    let mut a = Buffer::new::<4>();
+    let mut b = Buffer::new::<8>();
+    a.push_str("ab").unwrap();
+    b.push_str("ab").unwrap();
+
+    assert!(a == b);
+    assert_eq!(a.partial_cmp(&b), Some(Ordering::Equal));
+
+    b.push_str("c").unwrap();
+    assert!(a != b);
+    assert!(a < b);
+
+    let mut items = ["b", "", "ab", "a"].map(|s| {
+        let mut buffer = Buffer::new::<2>();
+        buffer.push_str(s).unwrap();
+        buffer
+    });
+    items.sort();
+    assert_eq!(items.each_ref().map(Buffer::as_str), ["", "a", "ab", "b"]);
+}
+
 #[cfg(kani)]
 #[kani::proof]
 #[kani::unwind(4)]
@@ -0,0 +1,28 @@
+use ::alloc::string::String;
+
+use super::Buffer;
+use crate::ByteBuffer;
+
+// `to_string` comes from `Display` via `alloc::string::ToString`
+impl<B: ByteBuffer> Buffer<B> {
+    pub fn into_string(self) -> String {
+        String::from(self.as_str())
+    }
+}
+
+impl<B: ByteBuffer> From<Buffer<B>> for String {
+    fn from(buffer: Buffer<B>) -> Self {
+        buffer.into_string()
+    }
+}
+
+#[test]
+fn test_to_string() {
+    use ::alloc::string::ToString;
+
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("héllo").unwrap();
+
+    assert_eq!(buffer.to_string(), "héllo");
+    assert_eq!(String::from(buffer), "héllo");
+}
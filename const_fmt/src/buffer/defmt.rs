@@ -0,0 +1,9 @@
+use super::Buffer;
+use crate::ByteBuffer;
+
+// formats the written content as a `{=str}`, without copying it
+impl<B: ByteBuffer> ::defmt::Format for Buffer<B> {
+    fn format(&self, fmt: ::defmt::Formatter) {
+        ::defmt::write!(fmt, "{=str}", self.as_str())
+    }
+}
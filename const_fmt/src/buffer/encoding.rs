@@ -0,0 +1,295 @@
+use super::hex::HEX_UPPER;
+use super::{Buffer, BufferWriteFailed};
+use crate::ByteBuffer;
+
+const BASE64_STANDARD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_SAFE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const BASE32_STANDARD: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32_CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes `bytes` as standard RFC 4648 base64, padded with `=`.
+    ///
+    /// This always writes exactly `4 * bytes.len().div_ceil(3)` bytes.
+    pub const fn write_base64(&mut self, bytes: &[u8]) -> Result<(), BufferWriteFailed> {
+        self.write_base64_imp(bytes, BASE64_STANDARD)
+    }
+
+    /// Like [`write_base64`](Self::write_base64), but with the URL and filename safe
+    /// alphabet (`-` and `_` instead of `+` and `/`).
+    pub const fn write_base64_url(&mut self, bytes: &[u8]) -> Result<(), BufferWriteFailed> {
+        self.write_base64_imp(bytes, BASE64_URL_SAFE)
+    }
+
+    /// Writes `bytes` as standard RFC 4648 base32, uppercase and padded with `=`.
+    ///
+    /// This always writes exactly `8 * bytes.len().div_ceil(5)` bytes.
+    pub const fn write_base32(&mut self, bytes: &[u8]) -> Result<(), BufferWriteFailed> {
+        self.write_base32_imp(bytes, BASE32_STANDARD, true)
+    }
+
+    /// Like [`write_base32`](Self::write_base32), but with Crockford's alphabet, which
+    /// leaves out `I`, `L`, `O` and `U`, and without padding.
+    pub const fn write_base32_crockford(&mut self, bytes: &[u8]) -> Result<(), BufferWriteFailed> {
+        self.write_base32_imp(bytes, BASE32_CROCKFORD, false)
+    }
+
+    /// Writes `s` with every byte outside of the RFC 3986 unreserved set
+    /// (`A-Z a-z 0-9 - . _ ~`) percent-encoded as `%XX`.
+    pub const fn write_percent_encoded(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        self.write_percent_encoded_imp(s, false)
+    }
+
+    /// Like [`write_percent_encoded`](Self::write_percent_encoded), but also leaves the
+    /// characters allowed in a URL path (`/ : @ ! $ & ' ( ) * + , ; =`) as they are.
+    pub const fn write_percent_encoded_path(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        self.write_percent_encoded_imp(s, true)
+    }
+
+    const fn write_percent_encoded_imp(
+        &mut self,
+        s: &str,
+        path: bool,
+    ) -> Result<(), BufferWriteFailed> {
+        let bytes = s.as_bytes();
+        let mut len = 0usize;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            len = len.saturating_add(if is_url_safe(bytes[i], path) { 1 } else { 3 });
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let byte = bytes[i];
+
+            unsafe {
+                if is_url_safe(byte, path) {
+                    self.push_byte_unchecked(byte);
+                } else {
+                    self.push_byte_unchecked(b'%');
+                    self.push_hex_unchecked(byte as u64, 2, HEX_UPPER);
+                }
+            }
+
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    const fn write_base64_imp(
+        &mut self,
+        bytes: &[u8],
+        alphabet: &[u8; 64],
+    ) -> Result<(), BufferWriteFailed> {
+        if bytes.len().div_ceil(3) * 4 > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let remaining = bytes.len() - i;
+            let b0 = bytes[i] as u32;
+            let b1 = if remaining > 1 {
+                bytes[i + 1] as u32
+            } else {
+                0
+            };
+            let b2 = if remaining > 2 {
+                bytes[i + 2] as u32
+            } else {
+                0
+            };
+            let chunk = (b0 << 16) | (b1 << 8) | b2;
+
+            unsafe {
+                self.push_byte_unchecked(alphabet[(chunk >> 18) as usize & 0x3f]);
+                self.push_byte_unchecked(alphabet[(chunk >> 12) as usize & 0x3f]);
+
+                if remaining > 1 {
+                    self.push_byte_unchecked(alphabet[(chunk >> 6) as usize & 0x3f]);
+                } else {
+                    self.push_byte_unchecked(b'=');
+                }
+
+                if remaining > 2 {
+                    self.push_byte_unchecked(alphabet[chunk as usize & 0x3f]);
+                } else {
+                    self.push_byte_unchecked(b'=');
+                }
+            }
+
+            i += 3;
+        }
+
+        Ok(())
+    }
+
+    const fn write_base32_imp(
+        &mut self,
+        bytes: &[u8],
+        alphabet: &[u8; 32],
+        pad: bool,
+    ) -> Result<(), BufferWriteFailed> {
+        // each 5 byte chunk is 8 characters, and a partial one gets a character for each
+        // started group of 5 bits
+        let tail = bytes.len() % 5;
+        let tail_len = match (tail, pad) {
+            (0, _) => 0,
+            (_, true) => 8,
+            (_, false) => (tail * 8).div_ceil(5),
+        };
+
+        if (bytes.len() / 5 * 8).saturating_add(tail_len) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let remaining = bytes.len() - i;
+            let mut chunk = 0u64;
+            let mut j = 0;
+
+            while j < 5 {
+                let byte = if j < remaining { bytes[i + j] } else { 0 };
+                chunk = chunk << 8 | byte as u64;
+                j += 1;
+            }
+
+            let chars = if remaining >= 5 {
+                8
+            } else {
+                (remaining * 8).div_ceil(5)
+            };
+            let mut j = 0;
+
+            while j < 8 {
+                unsafe {
+                    if j < chars {
+                        self.push_byte_unchecked(alphabet[(chunk >> (35 - 5 * j)) as usize & 0x1f]);
+                    } else if pad {
+                        self.push_byte_unchecked(b'=');
+                    }
+                }
+
+                j += 1;
+            }
+
+            i += 5;
+        }
+
+        Ok(())
+    }
+}
+
+const fn is_url_safe(byte: u8, path: bool) -> bool {
+    match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => true,
+        b'/' | b':' | b'@' | b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b','
+        | b';' | b'=' => path,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+fn test_bytes(seed: u64) -> impl Iterator<Item = Vec<u8>> {
+    let mut random = crate::test_rng::random_u64s(seed);
+    (0..200).map(move |len| (&mut random).take(len % 67).map(|x| x as u8).collect())
+}
+
+#[test]
+fn test_base64() {
+    use base64::Engine;
+    use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+
+    for bytes in test_bytes(0x2545_f491_4f6c_dd1d) {
+        let mut buffer = Buffer::new::<92>();
+        buffer.write_base64(&bytes).unwrap();
+        assert_eq!(buffer.len(), bytes.len().div_ceil(3) * 4);
+        assert_eq!(STANDARD.decode(buffer.as_str()).unwrap(), bytes);
+
+        let mut buffer = Buffer::new::<92>();
+        buffer.write_base64_url(&bytes).unwrap();
+        assert_eq!(URL_SAFE.decode(buffer.as_str()).unwrap(), bytes);
+    }
+
+    let mut buffer = Buffer::new::<8>();
+    buffer.write_base64(b"foob").unwrap();
+    assert_eq!(buffer.as_str(), "Zm9vYg==");
+    assert!(buffer.write_base64(b"f").is_err());
+    assert_eq!(buffer.as_str(), "Zm9vYg==");
+}
+
+#[test]
+fn test_base32() {
+    use base32::Alphabet;
+
+    for bytes in test_bytes(0x9e37_79b9_7f4a_7c15) {
+        let mut buffer = Buffer::new::<112>();
+        buffer.write_base32(&bytes).unwrap();
+        assert_eq!(buffer.len(), bytes.len().div_ceil(5) * 8);
+        assert_eq!(
+            buffer.as_str(),
+            base32::encode(Alphabet::Rfc4648 { padding: true }, &bytes)
+        );
+        let decoded = base32::decode(Alphabet::Rfc4648 { padding: true }, buffer.as_str());
+        assert_eq!(decoded.unwrap(), bytes);
+
+        let mut buffer = Buffer::new::<112>();
+        buffer.write_base32_crockford(&bytes).unwrap();
+        assert_eq!(buffer.as_str(), base32::encode(Alphabet::Crockford, &bytes));
+        let decoded = base32::decode(Alphabet::Crockford, buffer.as_str());
+        assert_eq!(decoded.unwrap(), bytes);
+    }
+
+    // the test vectors from RFC 4648
+    let cases = [
+        ("", ""),
+        ("f", "MY======"),
+        ("fo", "MZXQ===="),
+        ("foo", "MZXW6==="),
+        ("foob", "MZXW6YQ="),
+        ("fooba", "MZXW6YTB"),
+        ("foobar", "MZXW6YTBOI======"),
+    ];
+
+    for (bytes, expected) in cases {
+        let mut buffer = Buffer::new::<16>();
+        buffer.write_base32(bytes.as_bytes()).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<8>();
+    assert!(buffer.write_base32(b"foobar").is_err());
+    assert!(buffer.write_base32_crockford(b"foobar").is_err());
+    buffer.write_base32_crockford(b"fooba").unwrap();
+    assert_eq!(buffer.as_str(), "CSQPYRK1");
+}
+
+#[test]
+fn test_percent_encoded() {
+    let mut buffer = Buffer::new::<64>();
+    buffer.write_percent_encoded("a b/c?d=é~").unwrap();
+    assert_eq!(buffer.as_str(), "a%20b%2Fc%3Fd%3D%C3%A9~");
+
+    buffer.clear();
+    buffer
+        .write_percent_encoded_path("/files/a b;v=1?x#y")
+        .unwrap();
+    assert_eq!(buffer.as_str(), "/files/a%20b;v=1%3Fx%23y");
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_percent_encoded("ab c").is_err());
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,511 @@
+use super::hex::HEX_LOWER;
+use super::{Buffer, BufferWriteFailed};
+use crate::ByteBuffer;
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes `s` as a double-quoted JSON string.
+    ///
+    /// `"` and `\` are backslash-escaped, as are `\n`, `\t`, `\r`, `\b` and `\f`.
+    /// Every other control character is written as `\u00XX`.
+    pub const fn write_json_string(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        let bytes = s.as_bytes();
+        let mut len = 2usize;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            len = len.saturating_add(json_escape_len(bytes[i]));
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_byte_unchecked(b'"') };
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let byte = bytes[i];
+
+            let escape = match byte {
+                b'"' => b'"',
+                b'\\' => b'\\',
+                b'\n' => b'n',
+                b'\t' => b't',
+                b'\r' => b'r',
+                0x08 => b'b',
+                0x0c => b'f',
+                _ => 0,
+            };
+
+            // non-ASCII bytes are copied as-is, so whole characters end up being copied
+            unsafe {
+                if escape != 0 {
+                    self.push_byte_unchecked(b'\\');
+                    self.push_byte_unchecked(escape);
+                } else if byte < 0x20 {
+                    self.push_str_unchecked("\\u00");
+                    self.push_hex_unchecked(byte as u64, 2, HEX_LOWER);
+                } else {
+                    self.push_byte_unchecked(byte);
+                }
+            }
+
+            i += 1;
+        }
+
+        unsafe { self.push_byte_unchecked(b'"') };
+
+        Ok(())
+    }
+
+    /// Writes the bytes of `s` as a double-quoted C string literal.
+    ///
+    /// `\n`, `\t`, `\\` and `\"` are backslash-escaped, and every other byte that isn't
+    /// printable ASCII (including all bytes of non-ASCII characters) is written as `\xHH`.
+    /// Since C's `\x` escapes take as many hex digits as they can, a hex digit right after
+    /// one is escaped as well.
+    pub const fn write_c_escaped(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        let bytes = s.as_bytes();
+        let mut len = 2usize;
+        let mut after_hex = false;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let escape = c_escape(bytes[i], after_hex);
+            len = len.saturating_add(escape.len());
+            after_hex = matches!(escape, CEscape::Hex);
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_byte_unchecked(b'"') };
+
+        let mut after_hex = false;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let byte = bytes[i];
+            let escape = c_escape(byte, after_hex);
+
+            unsafe {
+                match escape {
+                    CEscape::None => self.push_byte_unchecked(byte),
+                    CEscape::Short(escape) => {
+                        self.push_byte_unchecked(b'\\');
+                        self.push_byte_unchecked(escape);
+                    }
+                    CEscape::Hex => {
+                        self.push_str_unchecked("\\x");
+                        self.push_hex_unchecked(byte as u64, 2, HEX_LOWER);
+                    }
+                }
+            }
+
+            after_hex = matches!(escape, CEscape::Hex);
+            i += 1;
+        }
+
+        unsafe { self.push_byte_unchecked(b'"') };
+
+        Ok(())
+    }
+
+    /// Writes `s` between two `quote`s, escaping any `quote` inside it.
+    ///
+    /// With an `escape` character, each `quote` and `escape` in `s` is prefixed with it, so
+    /// `'` and `\` quote `it's` as `'it\'s'`. Without one, each `quote` is doubled instead,
+    /// like CSV does: `"` quotes `say "hi"` as `"say ""hi"""`. Nothing else is escaped.
+    pub const fn write_quoted(
+        &mut self,
+        s: &str,
+        quote: char,
+        escape: Option<char>,
+    ) -> Result<(), BufferWriteFailed> {
+        let mut quote_buf = [0; 4];
+        let quote = quote.encode_utf8(&mut quote_buf).as_bytes();
+        let mut escape_buf = [0; 4];
+        let escape = match escape {
+            Some(escape) => escape.encode_utf8(&mut escape_buf).as_bytes(),
+            None => quote,
+        };
+
+        // a whole encoded character can only match at the start of one, so matching the
+        // bytes finds exactly the characters
+        let bytes = s.as_bytes();
+        let mut len = bytes.len().saturating_add(2 * quote.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if starts_with_at(bytes, i, quote) || starts_with_at(bytes, i, escape) {
+                len = len.saturating_add(escape.len());
+            }
+
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        // `quote` and `escape` are whole characters, and all of `bytes` is copied, so this is UTF-8
+        unsafe {
+            self.push_str_unchecked(core::str::from_utf8_unchecked(quote));
+
+            let mut i = 0;
+
+            while i < bytes.len() {
+                if starts_with_at(bytes, i, quote) || starts_with_at(bytes, i, escape) {
+                    self.push_str_unchecked(core::str::from_utf8_unchecked(escape));
+                }
+
+                self.push_byte_unchecked(bytes[i]);
+                i += 1;
+            }
+
+            self.push_str_unchecked(core::str::from_utf8_unchecked(quote));
+        }
+
+        Ok(())
+    }
+
+    /// Writes `s` as a CSV field, quoted with `"` and with embedded quotes doubled only if
+    /// it contains a `,`, a `"`, or a line break, as RFC 4180 requires. Any other field is
+    /// written as it is.
+    pub const fn write_csv_field(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        if csv_needs_quotes(s) {
+            self.write_quoted(s, '"', None)
+        } else {
+            self.push_str(s)
+        }
+    }
+
+    /// Writes `fields` as one CSV row, each one like
+    /// [`write_csv_field`](Self::write_csv_field) and separated by `,`, without a line
+    /// ending.
+    ///
+    /// A row of a single empty field is written as `""`, so that it isn't an empty line.
+    /// Nothing is written if the whole row doesn't fit.
+    pub const fn write_csv_row(&mut self, fields: &[&str]) -> Result<(), BufferWriteFailed> {
+        if fields.len() == 1 && fields[0].is_empty() {
+            return self.push_str("\"\"");
+        }
+
+        let mut len = fields.len().saturating_sub(1);
+        let mut i = 0;
+
+        while i < fields.len() {
+            len = len.saturating_add(csv_field_len(fields[i]));
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < fields.len() {
+            if i != 0 {
+                unsafe { self.push_byte_unchecked(b',') };
+            }
+
+            // can't fail, there is room
+            let _ = self.write_csv_field(fields[i]);
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `s` as a single-quoted POSIX shell word, so the shell reads it back unchanged.
+    ///
+    /// Nothing is special inside single quotes, so each `'` in `s` is written as `'\''`,
+    /// which closes the quotes, writes an escaped quote and opens them again.
+    pub const fn write_shell_quoted(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        let bytes = s.as_bytes();
+        let mut len = bytes.len().saturating_add(2);
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i] == b'\'' {
+                len = len.saturating_add(r"'\''".len() - 1);
+            }
+
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_byte_unchecked(b'\'') };
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            unsafe {
+                if bytes[i] == b'\'' {
+                    self.push_str_unchecked(r"'\''");
+                } else {
+                    self.push_byte_unchecked(bytes[i]);
+                }
+            }
+
+            i += 1;
+        }
+
+        unsafe { self.push_byte_unchecked(b'\'') };
+
+        Ok(())
+    }
+}
+
+const fn csv_needs_quotes(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if matches!(bytes[i], b',' | b'"' | b'\n' | b'\r') {
+            return true;
+        }
+
+        i += 1;
+    }
+
+    false
+}
+
+// the length of `write_csv_field`
+const fn csv_field_len(s: &str) -> usize {
+    if !csv_needs_quotes(s) {
+        return s.len();
+    }
+
+    let bytes = s.as_bytes();
+    let mut len = s.len() + 2;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        len += (bytes[i] == b'"') as usize;
+        i += 1;
+    }
+
+    len
+}
+
+// whether `bytes[i..]` starts with `needle`
+const fn starts_with_at(bytes: &[u8], i: usize, needle: &[u8]) -> bool {
+    if needle.len() > bytes.len() - i {
+        return false;
+    }
+
+    let mut j = 0;
+
+    while j < needle.len() {
+        if bytes[i + j] != needle[j] {
+            return false;
+        }
+
+        j += 1;
+    }
+
+    true
+}
+
+enum CEscape {
+    None,
+    Short(u8),
+    Hex,
+}
+
+impl CEscape {
+    const fn len(&self) -> usize {
+        match self {
+            CEscape::None => 1,
+            CEscape::Short(_) => 2,
+            CEscape::Hex => 4,
+        }
+    }
+}
+
+const fn c_escape(byte: u8, after_hex: bool) -> CEscape {
+    match byte {
+        b'\n' => CEscape::Short(b'n'),
+        b'\t' => CEscape::Short(b't'),
+        b'\\' => CEscape::Short(b'\\'),
+        b'"' => CEscape::Short(b'"'),
+        _ if after_hex && byte.is_ascii_hexdigit() => CEscape::Hex,
+        b' '..=b'~' => CEscape::None,
+        _ => CEscape::Hex,
+    }
+}
+
+const fn json_escape_len(byte: u8) -> usize {
+    match byte {
+        b'"' | b'\\' | b'\n' | b'\t' | b'\r' | 0x08 | 0x0c => 2,
+        0..0x20 => 6,
+        _ => 1,
+    }
+}
+
+#[test]
+fn test_json_string() {
+    let cases = [
+        "",
+        "hello",
+        "quote \" backslash \\ slash /",
+        "\n\t\r\u{8}\u{c}",
+        "\0\u{1}\u{1f}\u{7f}",
+        "héllo wörld ✓ 🦀",
+    ];
+
+    for s in cases {
+        let mut buffer = Buffer::new::<64>();
+        buffer.write_json_string(s).unwrap();
+        assert_eq!(buffer.as_str(), serde_json::to_string(s).unwrap());
+    }
+
+    let mut buffer = Buffer::new::<7>();
+    assert!(buffer.write_json_string("\0").is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_c_escaped() {
+    let cases = [
+        ("", r#""""#),
+        ("hello, world", r#""hello, world""#),
+        ("a\nb\tc", r#""a\nb\tc""#),
+        (r#"say "hi" \o/"#, r#""say \"hi\" \\o/""#),
+        ("\0\r\u{1b}\u{7f}", r#""\x00\x0d\x1b\x7f""#),
+        // hex digits can't directly follow a `\x` escape
+        ("\u{1}a\u{1}g\u{1}9", r#""\x01\x61\x01g\x01\x39""#),
+        ("é", r#""\xc3\xa9""#),
+        ("✓ ok", r#""\xe2\x9c\x93 ok""#),
+        ("'?~", r#""'?~""#),
+    ];
+
+    for (s, expected) in cases {
+        let mut buffer = Buffer::new::<64>();
+        buffer.write_c_escaped(s).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{s:?}");
+    }
+
+    let mut buffer = Buffer::new::<9>();
+    assert!(buffer.write_c_escaped("\u{1}a").is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_shell_quoted() {
+    let cases = [
+        ("", "''"),
+        ("hello world", "'hello world'"),
+        ("it's", r"'it'\''s'"),
+        ("''", r"''\'''\'''"),
+        (r#"$HOME `ls` \ "ok""#, r#"'$HOME `ls` \ "ok"'"#),
+        ("héllo\n✓", "'héllo\n✓'"),
+    ];
+
+    for (s, expected) in cases {
+        let mut buffer = Buffer::new::<64>();
+        buffer.write_shell_quoted(s).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{s:?}");
+    }
+
+    let mut buffer = Buffer::new::<6>();
+    assert!(buffer.write_shell_quoted("'a").is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_quoted() {
+    let cases: &[(&str, char, Option<char>, &str)] = &[
+        ("", '"', None, r#""""#),
+        ("plain", '"', None, r#""plain""#),
+        (r#"say "hi""#, '"', None, r#""say ""hi""""#),
+        ("it's", '\'', Some('\\'), r"'it\'s'"),
+        (r"a\b", '\'', Some('\\'), r"'a\\b'"),
+        (r"a\b", '"', None, r#""a\b""#),
+        ("«x» ok", '»', Some('§'), "»«x§» ok»"),
+        ("é§'", '\'', Some('§'), "'é§§§''"),
+    ];
+
+    for &(s, quote, escape, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.write_quoted(s, quote, escape).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{s:?}");
+    }
+
+    // CSV quoting reads back with a CSV style unquote
+    let field = r#"a "b", "c""#;
+    let mut buffer = Buffer::new::<32>();
+    buffer.write_quoted(field, '"', None).unwrap();
+    let inner = &buffer[1..buffer.len() - 1];
+    assert_eq!(inner.replace(r#""""#, r#"""#), field);
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_quoted("a'", '\'', Some('\\')).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_csv() {
+    let cases = [
+        ("", ""),
+        ("plain text", "plain text"),
+        ("a,b", r#""a,b""#),
+        (r#"say "hi""#, r#""say ""hi""""#),
+        ("two\nlines", "\"two\nlines\""),
+        ("cr\r", "\"cr\r\""),
+        ("  spaces  ", "  spaces  "),
+        ("é;✓", "é;✓"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for (s, expected) in cases {
+        buffer.clear();
+        buffer.write_csv_field(s).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{s:?}");
+    }
+
+    let alphabet = ['a', ',', '"', '\n', '\r', ' ', 'é'];
+    let mut random = crate::test_rng::random_u64s(0x853c_49e6_748f_ea9b);
+    let mut buffer = Buffer::new::<256>();
+
+    for _ in 0..500 {
+        let count = random.next().unwrap() % 5 + 1;
+        let fields: Vec<String> = (0..count)
+            .map(|_| {
+                let len = random.next().unwrap() % 6;
+                (0..len)
+                    .map(|_| alphabet[(random.next().unwrap() % 7) as usize])
+                    .collect()
+            })
+            .collect();
+        let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.write_record(&fields).unwrap();
+        let mut expected = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(expected.pop(), Some('\n'));
+
+        buffer.clear();
+        buffer.write_csv_row(&fields).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{fields:?}");
+    }
+
+    let mut buffer = Buffer::new::<6>();
+    buffer.write_csv_row(&[]).unwrap();
+    assert!(buffer.is_empty());
+    assert!(buffer.write_csv_row(&["a", "b,c"]).is_err());
+    assert!(buffer.is_empty());
+    buffer.write_csv_row(&[""]).unwrap();
+    assert_eq!(buffer.as_str(), r#""""#);
+}
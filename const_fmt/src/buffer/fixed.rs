@@ -0,0 +1,695 @@
+use super::float::RoundingMode;
+use super::{Buffer, BufferWriteFailed, u64_digit_count, u128_digit_count};
+use crate::ByteBuffer;
+
+// `num / den` rounded to a fixed number of fractional digits, found with a
+// first pass of long division so the digits can be written in a second one
+struct Ratio {
+    int: u64,
+    rem: u64,
+    den: u64,
+    round_up: bool,
+    // the last fractional digit that isn't a 9, which absorbs the carry when rounding up
+    last_non_nine: Option<usize>,
+}
+
+impl Ratio {
+    const fn new(num: u64, den: u64, precision: usize, mode: RoundingMode) -> Self {
+        let mut rem = num % den;
+        let mut last_digit = num / den % 10;
+        let mut last_non_nine = None;
+        let mut i = 0;
+
+        while i < precision {
+            let cur = rem as u128 * 10;
+            last_digit = (cur / den as u128) as u64;
+            rem = (cur % den as u128) as u64;
+
+            if last_digit != 9 {
+                last_non_nine = Some(i);
+            }
+
+            i += 1;
+        }
+
+        let twice = rem as u128 * 2;
+        let half = if twice < den as u128 {
+            core::cmp::Ordering::Less
+        } else if twice == den as u128 {
+            core::cmp::Ordering::Equal
+        } else {
+            core::cmp::Ordering::Greater
+        };
+        let round_up = mode.round_up(half, rem == 0, last_digit % 2 == 1);
+
+        // a remainder means `den > 1`, so this can't overflow
+        let carry = round_up && last_non_nine.is_none();
+
+        Self {
+            int: num / den + carry as u64,
+            rem: num % den,
+            den,
+            round_up,
+            last_non_nine,
+        }
+    }
+
+    const fn fixed_len(&self, precision: usize, point: char) -> usize {
+        fixed_len(self.int, precision, point)
+    }
+
+    const fn digits(&self) -> RatioDigits {
+        RatioDigits {
+            rem: self.rem,
+            index: 0,
+        }
+    }
+}
+
+// the rounded fractional digits of a `Ratio`, one at a time
+struct RatioDigits {
+    rem: u64,
+    index: usize,
+}
+
+impl RatioDigits {
+    // must be called at most as many times as the precision the ratio was rounded to
+    const fn next(&mut self, ratio: &Ratio) -> u8 {
+        let cur = self.rem as u128 * 10;
+        let mut digit = (cur / ratio.den as u128) as u8;
+        self.rem = (cur % ratio.den as u128) as u64;
+
+        if ratio.round_up {
+            digit = match ratio.last_non_nine {
+                Some(last) if self.index == last => digit + 1,
+                Some(last) if self.index < last => digit,
+                _ => 0,
+            };
+        }
+
+        self.index += 1;
+        digit
+    }
+}
+
+// the length of `int` followed by `point` and `precision` fractional digits
+const fn fixed_len(int: u64, precision: usize, point: char) -> usize {
+    let point = if precision == 0 { 0 } else { point.len_utf8() };
+    (u64_digit_count(int) + point).saturating_add(precision)
+}
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes `num / den` with exactly `precision` fractional digits, computed with integer
+    /// long division and rounded half to even.
+    ///
+    /// Fails without writing anything if `den` is zero.
+    pub const fn write_ratio(
+        &mut self,
+        num: u64,
+        den: u64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_ratio_imp(num, den, precision, '.', RoundingMode::HalfEven)
+    }
+
+    /// Like [`write_ratio`](Self::write_ratio), but rounding with `mode` instead of half to
+    /// even.
+    pub const fn write_ratio_rounded(
+        &mut self,
+        num: u64,
+        den: u64,
+        precision: usize,
+        mode: RoundingMode,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_ratio_imp(num, den, precision, '.', mode)
+    }
+
+    /// Like [`write_ratio`](Self::write_ratio), but with `point` as the decimal separator.
+    pub const fn write_ratio_with_sep(
+        &mut self,
+        num: u64,
+        den: u64,
+        precision: usize,
+        point: char,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_ratio_imp(num, den, precision, point, RoundingMode::HalfEven)
+    }
+
+    /// Writes a decimal given as its parts, `integer.fraction` with `fraction` zero padded
+    /// to exactly `frac_digits` digits, so `3`, `14` and `3` is `3.014`. No floating point
+    /// is involved, so the output is exactly the parts given.
+    ///
+    /// The sign comes from `integer`, so `-3`, `14` and `3` is `-3.014`. A value between
+    /// `-1` and `0` has no negative integer part, so write it as
+    /// [`write_sign(true)`](Self::write_sign) followed by a zero `integer`. A `frac_digits`
+    /// of `0` writes only `integer`, without a decimal point.
+    ///
+    /// Fails without writing anything if `fraction` has more than `frac_digits` digits.
+    pub const fn write_fixed_point(
+        &mut self,
+        integer: i128,
+        fraction: u64,
+        frac_digits: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        let fraction_len = u64_digit_count(fraction);
+
+        if fraction_len > frac_digits && !(fraction == 0 && frac_digits == 0) {
+            return Err(BufferWriteFailed);
+        }
+
+        let negative = integer < 0;
+        let point = (frac_digits != 0) as usize;
+        let len = (negative as usize + u128_digit_count(integer.unsigned_abs()) + point)
+            .saturating_add(frac_digits);
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        // can't fail, there is room
+        let _ = self.write_i128(integer);
+
+        if frac_digits == 0 {
+            return Ok(());
+        }
+
+        unsafe {
+            self.push_byte_unchecked(b'.');
+
+            let mut i = fraction_len;
+
+            while i < frac_digits {
+                self.push_byte_unchecked(b'0');
+                i += 1;
+            }
+        }
+
+        self.write_u64(fraction)
+    }
+
+    /// Writes an integer that stores a fixed-point value scaled by `10^scale`, with the
+    /// decimal point `scale` digits from the right, so `12345` with scale `3` is `12.345`
+    /// and `-5` with scale `3` is `-0.005`. A `scale` of `0` writes `value` as it is.
+    pub const fn write_scaled(&mut self, value: i64, scale: u32) -> Result<(), BufferWriteFailed> {
+        let negative = value < 0;
+        let magnitude = value.unsigned_abs();
+
+        // `u64::MAX` has 20 digits, so any larger scale leaves no integer part
+        let (integer, fraction) = if scale < 20 {
+            let divisor = 10u64.pow(scale);
+            (magnitude / divisor, magnitude % divisor)
+        } else {
+            (0, magnitude)
+        };
+
+        let point = (scale != 0) as usize;
+        let len =
+            (negative as usize + u64_digit_count(integer) + point).saturating_add(scale as usize);
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        // can't fail, there is room
+        let _ = self.write_sign(negative);
+        self.write_fixed_point(integer as i128, fraction, scale as usize)
+    }
+
+    /// Writes an amount of cents as currency, with the sign before `symbol`, `sep` between
+    /// every three digits of the whole part, and exactly two decimals, so `-123456` with
+    /// `"$"` and `','` is `-$1,234.56`.
+    pub const fn write_currency(
+        &mut self,
+        amount_cents: i64,
+        symbol: &str,
+        sep: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let cents = amount_cents.unsigned_abs();
+        let whole = cents / 100;
+        let digits = u64_digit_count(whole);
+        let len = (amount_cents < 0) as usize
+            + symbol.len()
+            + digits
+            + (digits - 1) / 3 * sep.len_utf8()
+            + ".00".len();
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        if amount_cents < 0 {
+            tri!(self.push_str("-"));
+        }
+
+        tri!(self.push_str(symbol));
+        tri!(self.write_u64_formatted(whole, 1, 3, sep));
+        tri!(self.push_str("."));
+        self.write_u64_formatted(cents % 100, 2, 0, sep)
+    }
+
+    /// Writes `done / total` as a percentage with exactly `precision` fractional digits,
+    /// so `3` of `4` at precision 2 is `75.00%`.
+    ///
+    /// This uses integer long division, rounded half to even like
+    /// [`write_ratio`](Self::write_ratio). Fails without writing anything if `total` is
+    /// zero.
+    pub const fn write_progress(
+        &mut self,
+        done: u64,
+        total: u64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        if total == 0 {
+            return Err(BufferWriteFailed);
+        }
+
+        // the shortest this can be, checked before the long division
+        let point = (precision != 0) as usize;
+
+        if precision.saturating_add(point + "0%".len()) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        // the first two fractional digits of the ratio join its integer part
+        let ratio = Ratio::new(done, total, precision + 2, RoundingMode::HalfEven);
+        let mut digits = ratio.digits();
+        let tens = digits.next(&ratio) as u128;
+        let ones = digits.next(&ratio) as u128;
+        let int = ratio.int as u128 * 100 + tens * 10 + ones;
+
+        if (u128_digit_count(int) + point + precision + "%".len()) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        // can't fail, there is room
+        let _ = self.write_u128(int);
+
+        unsafe {
+            if precision != 0 {
+                self.push_byte_unchecked(b'.');
+            }
+
+            let mut i = 0;
+
+            while i < precision {
+                self.push_byte_unchecked(b'0' + digits.next(&ratio));
+                i += 1;
+            }
+
+            self.push_byte_unchecked(b'%');
+        }
+
+        Ok(())
+    }
+
+    /// Writes `n` abbreviated with a decimal suffix, like `999`, `1.2k`, `3.4M`, `5B` or
+    /// `7.0T`.
+    ///
+    /// Counts below `1000` are written as they are. Larger ones get `precision` fractional
+    /// digits, rounded half up, moving to the next suffix if the rounding reaches `1000`, so
+    /// `999_999` at precision 1 is `1.0M`.
+    pub const fn write_count_short(
+        &mut self,
+        n: u64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        const SUFFIXES: [&str; 5] = ["", "k", "M", "B", "T"];
+
+        if n < 1000 {
+            return self.write_u64(n);
+        }
+
+        let mut unit = 0;
+        let mut scale = 1;
+
+        while unit + 1 < SUFFIXES.len() && n / scale >= 1000 {
+            scale *= 1000;
+            unit += 1;
+        }
+
+        // as in `write_ratio_imp`, bail before the long division if it can't fit anyway. The
+        // rounding can still move to the next suffix, so only the shortest output, a single
+        // digit with a one byte suffix like `1.0k`, is a safe lower bound
+        if fixed_len(1, precision, '.').saturating_add(1) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut ratio = Ratio::new(n, scale, precision, RoundingMode::HalfUp);
+
+        if ratio.int >= 1000 && unit + 1 < SUFFIXES.len() {
+            scale *= 1000;
+            unit += 1;
+            ratio = Ratio::new(n, scale, precision, RoundingMode::HalfUp);
+        }
+
+        let suffix = SUFFIXES[unit];
+
+        if ratio.fixed_len(precision, '.').saturating_add(suffix.len()) > self.remaining_capacity()
+        {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_ratio_unchecked(&ratio, precision, '.');
+            self.push_str_unchecked(suffix);
+        }
+
+        Ok(())
+    }
+
+    const fn write_ratio_imp(
+        &mut self,
+        num: u64,
+        den: u64,
+        precision: usize,
+        point: char,
+        mode: RoundingMode,
+    ) -> Result<(), BufferWriteFailed> {
+        if den == 0 {
+            return Err(BufferWriteFailed);
+        }
+
+        // rounding can only make this longer, so bail before the long division if even the
+        // unrounded ratio doesn't fit
+        if fixed_len(num / den, precision, point) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let ratio = Ratio::new(num, den, precision, mode);
+
+        if ratio.fixed_len(precision, point) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_ratio_unchecked(&ratio, precision, point) };
+
+        Ok(())
+    }
+
+    // there must be room for `ratio.fixed_len(precision, point)` bytes
+    const unsafe fn push_ratio_unchecked(&mut self, ratio: &Ratio, precision: usize, point: char) {
+        // can't fail, the caller made room
+        let _ = self.write_u64(ratio.int);
+
+        if precision != 0 {
+            let _ = self.write_char(point);
+        }
+
+        let mut digits = ratio.digits();
+        let mut i = 0;
+
+        while i < precision {
+            unsafe { self.push_byte_unchecked(b'0' + digits.next(ratio)) };
+            i += 1;
+        }
+    }
+}
+
+#[test]
+fn test_ratio() {
+    let cases: &[(u64, u64, usize, &str)] = &[
+        (1, 3, 3, "0.333"),
+        (2, 3, 3, "0.667"),
+        (1, 8, 2, "0.12"),
+        (3, 8, 2, "0.38"),
+        (1, 2, 0, "0"),
+        (3, 2, 0, "2"),
+        (7, 1, 2, "7.00"),
+        (0, 5, 1, "0.0"),
+        (1999, 2000, 2, "1.00"),
+        (19999, 20000, 3, "1.000"),
+        (1, 7, 12, "0.142857142857"),
+        (u64::MAX, 1, 1, "18446744073709551615.0"),
+        (u64::MAX, u64::MAX - 1, 20, "1.00000000000000000005"),
+        (u64::MAX - 1, u64::MAX, 3, "1.000"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for &(num, den, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_ratio(num, den, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{num}/{den}");
+    }
+
+    // check that long division agrees with exact rational rounding
+    for num in 0..200u64 {
+        for den in 1..40u64 {
+            let scaled = num as u128 * 1000;
+            let (q, r) = (scaled / den as u128, scaled % den as u128);
+            let round_up = 2 * r > den as u128 || (2 * r == den as u128 && q % 2 == 1);
+            let expected = q + round_up as u128;
+
+            buffer.clear();
+            buffer.write_ratio(num, den, 3).unwrap();
+            assert_eq!(
+                buffer.as_str(),
+                format!("{}.{:03}", expected / 1000, expected % 1000)
+            );
+        }
+    }
+
+    assert!(buffer.write_ratio(1, 0, 2).is_err());
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_ratio(19999, 20000, 3).is_err());
+    assert!(buffer.is_empty());
+
+    // this has to fail before doing any of the long division
+    let mut buffer = Buffer::new::<64>();
+    assert!(buffer.write_ratio(1, 3, usize::MAX).is_err());
+    assert!(buffer.write_ratio(1, 3, 63).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_ratio_with_sep() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.write_ratio_with_sep(1, 3, 3, ',').unwrap();
+    assert_eq!(buffer.as_str(), "0,333");
+
+    buffer.clear();
+    buffer.write_ratio_with_sep(5, 2, 0, ',').unwrap();
+    assert_eq!(buffer.as_str(), "2");
+
+    buffer.clear();
+    buffer.write_ratio_with_sep(7, 4, 2, '٫').unwrap();
+    assert_eq!(buffer.as_str(), "1٫75");
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_ratio_with_sep(7, 4, 2, '٫').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_ratio_rounded() {
+    use RoundingMode::*;
+
+    let cases: &[(u64, u64, [&str; 5])] = &[
+        (1, 8, ["0.12", "0.13", "0.12", "0.12", "0.13"]),
+        (3, 8, ["0.38", "0.38", "0.37", "0.37", "0.38"]),
+        (1, 3, ["0.33", "0.33", "0.33", "0.33", "0.34"]),
+        (2, 3, ["0.67", "0.67", "0.67", "0.66", "0.67"]),
+        (1, 4, ["0.25", "0.25", "0.25", "0.25", "0.25"]),
+        (1999, 2000, ["1.00", "1.00", "1.00", "0.99", "1.00"]),
+    ];
+
+    let mut buffer = Buffer::new::<16>();
+
+    for &(num, den, expected) in cases {
+        for (mode, expected) in [HalfEven, HalfUp, HalfDown, TowardZero, AwayFromZero]
+            .into_iter()
+            .zip(expected)
+        {
+            buffer.clear();
+            buffer.write_ratio_rounded(num, den, 2, mode).unwrap();
+            assert_eq!(buffer.as_str(), expected, "{num}/{den} {mode:?}");
+        }
+    }
+
+    for (mode, expected) in [(HalfEven, "2"), (HalfUp, "3"), (HalfDown, "2")] {
+        buffer.clear();
+        buffer.write_ratio_rounded(5, 2, 0, mode).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+}
+
+#[test]
+fn test_fixed_point() {
+    let cases = [
+        (3, 14, 3, "3.014"),
+        (3, 14, 2, "3.14"),
+        (-3, 14, 3, "-3.014"),
+        (0, 0, 2, "0.00"),
+        (0, 5, 1, "0.5"),
+        (42, 0, 0, "42"),
+        (-42, 0, 0, "-42"),
+        (1, 1, 25, "1.0000000000000000000000001"),
+        (
+            i128::MIN,
+            u64::MAX,
+            20,
+            &format!("{}.{}", i128::MIN, u64::MAX),
+        ),
+    ];
+
+    let mut buffer = Buffer::new::<64>();
+
+    for (integer, fraction, frac_digits, expected) in cases {
+        buffer.clear();
+        buffer
+            .write_fixed_point(integer, fraction, frac_digits)
+            .unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    // values between -1 and 0 get their sign separately
+    buffer.clear();
+    buffer.write_sign(true).unwrap();
+    buffer.write_fixed_point(0, 25, 2).unwrap();
+    assert_eq!(buffer.as_str(), "-0.25");
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_fixed_point(-3, 14, 3).is_err());
+    assert!(buffer.write_fixed_point(0, 0, usize::MAX).is_err());
+    assert!(buffer.is_empty());
+
+    // a fraction that doesn't fit in `frac_digits` is rejected, however big the buffer
+    let mut buffer = Buffer::new::<16>();
+    assert!(buffer.write_fixed_point(1, 123, 2).is_err());
+    assert!(buffer.write_fixed_point(1, 1, 0).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_scaled() {
+    let cases = [
+        (12345, 3, "12.345"),
+        (5, 3, "0.005"),
+        (-5, 3, "-0.005"),
+        (-12345, 2, "-123.45"),
+        (0, 2, "0.00"),
+        (42, 0, "42"),
+        (-1000, 3, "-1.000"),
+        (i64::MIN, 19, "-0.9223372036854775808"),
+        (i64::MAX, 22, "0.0009223372036854775807"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for (value, scale, expected) in cases {
+        buffer.clear();
+        buffer.write_scaled(value, scale).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_scaled(-5, 3).is_err());
+    assert!(buffer.write_scaled(1, u32::MAX).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_currency() {
+    let cases = [
+        (-123456, "$", ',', "-$1,234.56"),
+        (0, "$", ',', "$0.00"),
+        (5, "$", ',', "$0.05"),
+        (-99, "$", ',', "-$0.99"),
+        (100000, "€", '.', "€1.000.00"),
+        (12345678, "", ' ', "123 456.78"),
+        (123456789, "£", '\u{202f}', "£1\u{202f}234\u{202f}567.89"),
+        (i64::MIN, "$", ',', "-$92,233,720,368,547,758.08"),
+        (i64::MAX, "", '_', "92_233_720_368_547_758.07"),
+    ];
+
+    for (cents, symbol, sep, expected) in cases {
+        let mut buffer = Buffer::new::<64>();
+        buffer.write_currency(cents, symbol, sep).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<9>();
+    assert!(buffer.write_currency(-123456, "$", ',').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_progress() {
+    let cases: &[(u64, u64, usize, &str)] = &[
+        (3, 4, 2, "75.00%"),
+        (0, 7, 1, "0.0%"),
+        (7, 7, 0, "100%"),
+        (1, 3, 3, "33.333%"),
+        (2, 3, 0, "67%"),
+        (1, 8, 0, "12%"),
+        (3, 8, 0, "38%"),
+        (19999, 20000, 0, "100%"),
+        (19999, 20000, 2, "100.00%"),
+        (19999, 20000, 3, "99.995%"),
+        (5, 2, 1, "250.0%"),
+        (u64::MAX, 1, 0, "1844674407370955161500%"),
+        (1, u64::MAX, 20, "0.00000000000000000542%"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for &(done, total, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_progress(done, total, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{done}/{total}");
+    }
+
+    buffer.clear();
+    assert!(buffer.write_progress(1, 0, 2).is_err());
+
+    let mut buffer = Buffer::new::<6>();
+    assert!(buffer.write_progress(1, 1, 2).is_err());
+    assert!(buffer.write_progress(1, 1, usize::MAX).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_count_short() {
+    let cases: &[(u64, usize, &str)] = &[
+        (0, 1, "0"),
+        (999, 1, "999"),
+        (1000, 1, "1.0k"),
+        (1234, 1, "1.2k"),
+        (1250, 1, "1.3k"),
+        (3_400_000, 1, "3.4M"),
+        (5_000_000_000, 0, "5B"),
+        (999_949, 1, "999.9k"),
+        (999_950, 1, "1.0M"),
+        (999_999, 1, "1.0M"),
+        (999_999_999_999, 0, "1T"),
+        (7_000_000_000_000, 1, "7.0T"),
+        (u64::MAX, 2, "18446744.07T"),
+        (12_345, 3, "12.345k"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for &(n, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_count_short(n, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{n}");
+    }
+
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_count_short(1234, 1).is_err());
+    assert!(buffer.write_count_short(1234, usize::MAX).is_err());
+    assert!(buffer.is_empty());
+
+    // rounding up into the next suffix makes the output shorter, so it fits exactly
+    let mut buffer = Buffer::new::<4>();
+    buffer.write_count_short(999_999, 1).unwrap();
+    assert_eq!(buffer.as_str(), "1.0M");
+
+    let mut buffer = Buffer::new::<2>();
+    buffer.write_count_short(999_999_999_999, 0).unwrap();
+    assert_eq!(buffer.as_str(), "1T");
+    buffer.clear();
+    assert!(buffer.write_count_short(12_345, 0).is_err());
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,2390 @@
+use super::hex::HEX_LOWER;
+use super::pad::{Align, Padding};
+use super::{Buffer, BufferWriteFailed, u64_digit_count};
+use crate::ByteBuffer;
+
+/// How the fixed precision float and ratio writers round away the digits they drop.
+///
+/// Every mode works on the magnitude, so "up" means away from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Ties go to the even digit, like `std`.
+    #[default]
+    HalfEven,
+    /// Ties go away from zero.
+    HalfUp,
+    /// Ties go toward zero.
+    HalfDown,
+    /// Truncates.
+    TowardZero,
+    /// Rounds up anything that isn't exact.
+    AwayFromZero,
+}
+
+impl RoundingMode {
+    // whether to add one to the last kept digit, given the dropped digits compared to half of
+    // the last one's weight, whether they are all zero, and whether the last kept digit is odd
+    pub(super) const fn round_up(self, half: core::cmp::Ordering, exact: bool, odd: bool) -> bool {
+        use core::cmp::Ordering::*;
+
+        match self {
+            Self::HalfEven => matches!(half, Greater) || (matches!(half, Equal) && odd),
+            Self::HalfUp => !matches!(half, Less),
+            Self::HalfDown => matches!(half, Greater),
+            Self::TowardZero => false,
+            Self::AwayFromZero => !exact,
+        }
+    }
+}
+
+// enough base 10^9 limbs for the exact decimal expansion of any f64 (the smallest
+// subnormal has 767 significant digits), with room to scale it by a few powers of ten
+const LIMBS: usize = 96;
+const LIMB_BASE: u64 = 1_000_000_000;
+const POW10: [u32; 9] = [
+    1,
+    10,
+    100,
+    1_000,
+    10_000,
+    100_000,
+    1_000_000,
+    10_000_000,
+    100_000_000,
+];
+
+// an unsigned integer as little endian base 10^9 limbs, with no leading zero limbs
+#[derive(Clone, Copy)]
+struct Big {
+    limbs: [u32; LIMBS],
+    len: usize,
+}
+
+impl Big {
+    const ZERO: Self = Self {
+        limbs: [0; LIMBS],
+        len: 0,
+    };
+
+    const fn from_u64(value: u64) -> Self {
+        let mut big = Self::ZERO;
+        let mut value = value;
+
+        while value != 0 {
+            big.limbs[big.len] = (value % LIMB_BASE) as u32;
+            big.len += 1;
+            value /= LIMB_BASE;
+        }
+
+        big
+    }
+
+    // `x` must not be zero
+    const fn mul_small(&mut self, x: u32) {
+        let mut carry = 0;
+        let mut i = 0;
+
+        while i < self.len {
+            let cur = self.limbs[i] as u64 * x as u64 + carry;
+            self.limbs[i] = (cur % LIMB_BASE) as u32;
+            carry = cur / LIMB_BASE;
+            i += 1;
+        }
+
+        while carry != 0 {
+            self.limbs[self.len] = (carry % LIMB_BASE) as u32;
+            self.len += 1;
+            carry /= LIMB_BASE;
+        }
+    }
+
+    const fn mul_pow2(&mut self, n: u32) {
+        let mut n = n;
+
+        while n >= 31 {
+            self.mul_small(1 << 31);
+            n -= 31;
+        }
+
+        self.mul_small(1 << n);
+    }
+
+    const fn mul_pow5(&mut self, n: u32) {
+        let mut n = n;
+
+        while n >= 13 {
+            self.mul_small(5u32.pow(13));
+            n -= 13;
+        }
+
+        self.mul_small(5u32.pow(n));
+    }
+
+    const fn mul_pow10(&mut self, n: usize) {
+        let mut n = n;
+
+        while n >= 9 {
+            self.mul_small(LIMB_BASE as u32);
+            n -= 9;
+        }
+
+        self.mul_small(POW10[n]);
+    }
+
+    const fn add_small(&mut self, x: u32) {
+        let mut carry = x as u64;
+        let mut i = 0;
+
+        while carry != 0 {
+            if i == self.len {
+                self.limbs[i] = 0;
+                self.len += 1;
+            }
+
+            let cur = self.limbs[i] as u64 + carry;
+            self.limbs[i] = (cur % LIMB_BASE) as u32;
+            carry = cur / LIMB_BASE;
+            i += 1;
+        }
+    }
+
+    // divides by 10^n, dropping the remainder
+    const fn div_pow10(&mut self, n: usize) {
+        let whole = n / 9;
+
+        if whole >= self.len {
+            *self = Self::ZERO;
+            return;
+        }
+
+        let mut i = 0;
+
+        while i < self.len {
+            self.limbs[i] = if i + whole < self.len {
+                self.limbs[i + whole]
+            } else {
+                0
+            };
+            i += 1;
+        }
+
+        self.len -= whole;
+
+        let divisor = POW10[n % 9] as u64;
+        let mut rem = 0;
+        let mut i = self.len;
+
+        while i > 0 {
+            i -= 1;
+            let cur = rem * LIMB_BASE + self.limbs[i] as u64;
+            self.limbs[i] = (cur / divisor) as u32;
+            rem = cur % divisor;
+        }
+
+        while self.len > 0 && self.limbs[self.len - 1] == 0 {
+            self.len -= 1;
+        }
+    }
+
+    // `x * 2^exp` as an integer, scaled by `10^-exp` if `exp` is negative
+    const fn from_pow2(x: u64, exp: i32) -> Self {
+        let mut big = Self::from_u64(x);
+
+        if exp >= 0 {
+            big.mul_pow2(exp as u32);
+        } else {
+            big.mul_pow5(exp.unsigned_abs());
+        }
+
+        big
+    }
+
+    // `Ord::cmp` isn't const
+    const fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering::*;
+
+        if self.len != other.len {
+            return if self.len < other.len { Less } else { Greater };
+        }
+
+        let mut i = self.len;
+
+        while i > 0 {
+            i -= 1;
+
+            if self.limbs[i] != other.limbs[i] {
+                return if self.limbs[i] < other.limbs[i] {
+                    Less
+                } else {
+                    Greater
+                };
+            }
+        }
+
+        Equal
+    }
+
+    // the digit with weight 10^i
+    const fn digit(&self, i: usize) -> u8 {
+        let limb = i / 9;
+
+        if limb < self.len {
+            (self.limbs[limb] / POW10[i % 9] % 10) as u8
+        } else {
+            0
+        }
+    }
+
+    // the number of decimal digits, zero has none
+    const fn digit_count(&self) -> usize {
+        match self.len {
+            0 => 0,
+            len => (len - 1) * 9 + self.limbs[len - 1].ilog10() as usize + 1,
+        }
+    }
+
+    // whether all digits with a weight below 10^n are zero
+    const fn is_zero_below(&self, n: usize) -> bool {
+        let whole = n / 9;
+        let mut i = 0;
+
+        while i < whole && i < self.len {
+            if self.limbs[i] != 0 {
+                return false;
+            }
+
+            i += 1;
+        }
+
+        whole >= self.len || self.limbs[whole].is_multiple_of(POW10[n % 9])
+    }
+}
+
+// exactly `digits / 10^frac_digits`, negated if `negative`
+#[derive(Clone, Copy)]
+struct Decimal {
+    negative: bool,
+    digits: Big,
+    frac_digits: usize,
+}
+
+impl Decimal {
+    // NaNs and infinities have no decimal value
+    const fn from_f64(value: f64) -> Option<Self> {
+        let bits = value.to_bits();
+        let negative = bits >> 63 != 0;
+        let biased_exp = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+
+        if biased_exp == 0x7ff {
+            return None;
+        }
+
+        // value = mantissa * 2^exp
+        let (mut mantissa, mut exp) = if biased_exp == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | 1 << 52, biased_exp - 1075)
+        };
+
+        if mantissa != 0 {
+            let zeros = mantissa.trailing_zeros();
+            mantissa >>= zeros;
+            exp += zeros as i32;
+        }
+
+        let mut digits = Big::from_u64(mantissa);
+
+        // m * 2^-k is m * 5^k / 10^k
+        let frac_digits = if exp >= 0 {
+            digits.mul_pow2(exp as u32);
+            0
+        } else {
+            digits.mul_pow5(exp.unsigned_abs());
+            exp.unsigned_abs() as usize
+        };
+
+        Some(Self {
+            negative,
+            digits,
+            frac_digits,
+        })
+    }
+
+    const fn mul_pow10(&mut self, n: usize) {
+        if n <= self.frac_digits {
+            self.frac_digits -= n;
+        } else {
+            self.digits.mul_pow10(n - self.frac_digits);
+            self.frac_digits = 0;
+        }
+    }
+
+    // leaves at most `precision` fractional digits
+    const fn round(&mut self, precision: usize, mode: RoundingMode) {
+        if self.frac_digits <= precision {
+            return;
+        }
+
+        let dropped = self.frac_digits - precision;
+        let first = self.digits.digit(dropped - 1);
+        let rest_zero = self.digits.is_zero_below(dropped - 1);
+        let half = match first {
+            0..5 => core::cmp::Ordering::Less,
+            5 if rest_zero => core::cmp::Ordering::Equal,
+            _ => core::cmp::Ordering::Greater,
+        };
+        let exact = first == 0 && rest_zero;
+
+        self.digits.div_pow10(dropped);
+        self.frac_digits = precision;
+
+        if mode.round_up(half, exact, self.digits.digit(0) % 2 == 1) {
+            self.digits.add_small(1);
+        }
+    }
+
+    // multiplies by `10^exp`, which is exact either way
+    const fn scale_pow10(&mut self, exp: isize) {
+        if exp >= 0 {
+            self.mul_pow10(exp as usize);
+        } else {
+            self.frac_digits += exp.unsigned_abs();
+        }
+    }
+
+    // the power of ten of the most significant digit, zero has none
+    const fn leading_exp(&self) -> Option<isize> {
+        match self.digits.digit_count() {
+            0 => None,
+            digits => Some(digits as isize - 1 - self.frac_digits as isize),
+        }
+    }
+
+    // drops trailing zeros from the fractional digits
+    const fn trim(&mut self) {
+        while self.frac_digits > 0 && self.digits.digit(0) == 0 {
+            self.digits.div_pow10(1);
+            self.frac_digits -= 1;
+        }
+    }
+
+    // the number of digits before the decimal point, at least one
+    const fn int_len(&self) -> usize {
+        let digits = self.digits.digit_count();
+
+        if digits > self.frac_digits {
+            digits - self.frac_digits
+        } else {
+            1
+        }
+    }
+
+    // the length of `push_fixed_unchecked` without grouping
+    const fn fixed_len(&self, precision: usize, point: char) -> usize {
+        let point = if precision == 0 { 0 } else { point.len_utf8() };
+        (self.negative as usize + self.int_len() + point).saturating_add(precision)
+    }
+
+    // the length of the separators that `push_fixed_unchecked` adds with `group`
+    const fn group_len(&self, group: Option<char>) -> usize {
+        match group {
+            Some(sep) => (self.int_len() - 1) / 3 * sep.len_utf8(),
+            None => 0,
+        }
+    }
+}
+
+// how `write_fixed_imp` lays out a value
+struct FixedStyle<'a> {
+    rounding: RoundingMode,
+    point: char,
+    // drop trailing fractional zeros
+    trim: bool,
+    // write `value * 10^scale`
+    scale: usize,
+    suffix: &'a str,
+    // between every three integer digits
+    group: Option<char>,
+    nan: &'a str,
+    inf: &'a str,
+    neg_inf: &'a str,
+}
+
+impl FixedStyle<'static> {
+    const DEFAULT: Self = Self {
+        rounding: RoundingMode::HalfEven,
+        point: '.',
+        trim: false,
+        scale: 0,
+        suffix: "",
+        group: None,
+        nan: "NaN",
+        inf: "inf",
+        neg_inf: "-inf",
+    };
+}
+
+// the shortest decimal that reads back as the finite, nonzero `value`'s magnitude, as
+// `digits * 10^exp` with no trailing zeros in `digits`. Of the shortest candidates, the
+// one closest to `value` wins, and the larger one on a tie, like `std`'s `{:?}`.
+//
+// Every number strictly between the midpoints to `value`'s neighbours reads back as
+// `value` (and the midpoints themselves do when its mantissa is even), so this tries one
+// significant digit after another until rounding `value` down or up lands in that range.
+const fn shortest(value: f64) -> (u64, isize) {
+    let bits = value.to_bits();
+    let biased_exp = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+
+    let (mantissa, exp) = if biased_exp == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, biased_exp - 1075)
+    };
+
+    // the neighbour below a power of two is half as far away as the one above it
+    let low_gap = if fraction == 0 && biased_exp > 1 {
+        1
+    } else {
+        2
+    };
+    let inclusive = mantissa.is_multiple_of(2);
+
+    // the value and the midpoints as integers, all scaled by the same `10^frac_digits`
+    let exp = exp - 2;
+    let value = Big::from_pow2(4 * mantissa, exp);
+    let low = Big::from_pow2(4 * mantissa - low_gap, exp);
+    let high = Big::from_pow2(4 * mantissa + 2, exp);
+    let frac_digits = if exp < 0 {
+        exp.unsigned_abs() as usize
+    } else {
+        0
+    };
+
+    let digit_count = value.digit_count();
+    let mut precision = 1;
+
+    loop {
+        // a 17 digit candidate always fits, so this stops before running out of digits
+        let dropped = digit_count.saturating_sub(precision);
+
+        let mut down = value;
+        down.div_pow10(dropped);
+        let mut up = down;
+        up.add_small(1);
+
+        let nearer_up = dropped != 0 && {
+            let first = value.digit(dropped - 1);
+            let rest_zero = value.is_zero_below(dropped - 1);
+            let half = match first {
+                0..5 => core::cmp::Ordering::Less,
+                5 if rest_zero => core::cmp::Ordering::Equal,
+                _ => core::cmp::Ordering::Greater,
+            };
+
+            // like `std`, an exact tie goes up
+            RoundingMode::HalfUp.round_up(half, first == 0 && rest_zero, down.digit(0) % 2 == 1)
+        };
+
+        let (nearer, farther) = if nearer_up { (up, down) } else { (down, up) };
+
+        let digits = if in_range(nearer, dropped, &low, &high, inclusive) {
+            nearer
+        } else if dropped != 0 && in_range(farther, dropped, &low, &high, inclusive) {
+            farther
+        } else {
+            precision += 1;
+            continue;
+        };
+
+        // at most 18 digits, when rounding up carried into a new one
+        let mut limb = digits.len;
+        let mut digits_u64 = 0;
+
+        while limb > 0 {
+            limb -= 1;
+            digits_u64 = digits_u64 * LIMB_BASE + digits.limbs[limb] as u64;
+        }
+
+        let mut digits = digits_u64;
+        let mut exp = dropped as isize - frac_digits as isize;
+
+        while digits.is_multiple_of(10) {
+            digits /= 10;
+            exp += 1;
+        }
+
+        return (digits, exp);
+    }
+}
+
+// whether `digits * 10^dropped` is between `low` and `high`
+const fn in_range(digits: Big, dropped: usize, low: &Big, high: &Big, inclusive: bool) -> bool {
+    let mut candidate = digits;
+    candidate.mul_pow10(dropped);
+
+    let above = candidate.cmp(low);
+    let below = high.cmp(&candidate);
+
+    if inclusive {
+        !matches!(above, core::cmp::Ordering::Less) && !matches!(below, core::cmp::Ordering::Less)
+    } else {
+        matches!(above, core::cmp::Ordering::Greater)
+            && matches!(below, core::cmp::Ordering::Greater)
+    }
+}
+
+/// The integer part of `value`'s magnitude, so `-3.7` gives `3`.
+///
+/// Returns `None` for NaN, infinities, and magnitudes of `2^64` or more.
+pub const fn f64_integer_part(value: f64) -> Option<u64> {
+    let Some(decimal) = Decimal::from_f64(value) else {
+        return None;
+    };
+
+    let frac_digits = decimal.frac_digits;
+    let mut i = decimal.int_len();
+    let mut int: u64 = 0;
+
+    while i > 0 {
+        i -= 1;
+
+        int = match int.checked_mul(10) {
+            Some(int) => match int.checked_add(decimal.digits.digit(frac_digits + i) as u64) {
+                Some(int) => int,
+                None => return None,
+            },
+            None => return None,
+        };
+    }
+
+    Some(int)
+}
+
+/// The first `precision` fractional digits of `value`'s magnitude as an integer, so
+/// `3.14159` at precision 3 gives `141`.
+///
+/// The digits are truncated, not rounded, so they always belong with
+/// [`f64_integer_part`]. Returns `None` for NaN, infinities, and precisions over 19, the
+/// most digits a `u64` can always hold.
+pub const fn f64_fraction_digits(value: f64, precision: usize) -> Option<u64> {
+    let Some(decimal) = Decimal::from_f64(value) else {
+        return None;
+    };
+
+    if precision > 19 {
+        return None;
+    }
+
+    let frac_digits = decimal.frac_digits;
+    let mut digits = 0;
+    let mut i = 0;
+
+    while i < precision {
+        let digit = if i < frac_digits {
+            decimal.digits.digit(frac_digits - 1 - i)
+        } else {
+            0
+        };
+
+        digits = digits * 10 + digit as u64;
+        i += 1;
+    }
+
+    Some(digits)
+}
+
+// `log10(x)` for positive, finite `x`, since `f64::log10` isn't const. It splits `x` into
+// `m * 2^exp` with `m` in `[sqrt(1/2), sqrt(2))` and sums the series
+// `ln(m) = 2 * (z + z^3/3 + z^5/5 + ...)` with `z = (m - 1) / (m + 1)`, where `|z| < 0.172`
+// makes the terms past the 12th smaller than an ulp. The result is within a few ulps of
+// `std`'s.
+const fn log10(x: f64) -> f64 {
+    const MANTISSA_MASK: u64 = (1 << 52) - 1;
+
+    let mut bits = x.to_bits();
+    let mut exp = 0;
+
+    // subnormals get scaled into the normal range first
+    if bits >> 52 == 0 {
+        bits = (x * (1u64 << 54) as f64).to_bits();
+        exp = -54;
+    }
+
+    exp += (bits >> 52) as i64 - 1023;
+    let mut m = f64::from_bits(bits & MANTISSA_MASK | 1023 << 52);
+
+    if m > core::f64::consts::SQRT_2 {
+        m /= 2.0;
+        exp += 1;
+    }
+
+    let z = (m - 1.0) / (m + 1.0);
+    let z2 = z * z;
+    let mut term = z;
+    let mut sum = 0.0;
+    let mut i = 0;
+
+    while i < 12 {
+        sum += term / (2 * i + 1) as f64;
+        term *= z2;
+        i += 1;
+    }
+
+    (2.0 * sum + exp as f64 * core::f64::consts::LN_2) * core::f64::consts::LOG10_E
+}
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes `value` with exactly `precision` fractional digits, like `{:.N}`.
+    ///
+    /// The exact value of `value` is rounded half to even, so the output matches `std`.
+    /// NaN is written as `NaN` and infinities as `inf` and `-inf`.
+    ///
+    /// The sign of zero is always kept, also like `std`, so `-0.0` at precision 2 is
+    /// `-0.00`, and so is a negative value that rounds to zero, like `-0.001`. Every float
+    /// writer built on this one does the same.
+    pub const fn write_f64(
+        &mut self,
+        value: f64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_fixed_imp(value, precision, &FixedStyle::DEFAULT)
+    }
+
+    /// Like [`write_f64`](Self::write_f64), but with the given spellings for NaN and the
+    /// infinities, so they can be written as e.g. `nan`, `+Inf` and `-Inf`.
+    pub const fn write_f64_with(
+        &mut self,
+        value: f64,
+        precision: usize,
+        nan: &str,
+        inf: &str,
+        neg_inf: &str,
+    ) -> Result<(), BufferWriteFailed> {
+        let style = FixedStyle {
+            nan,
+            inf,
+            neg_inf,
+            ..FixedStyle::DEFAULT
+        };
+
+        self.write_fixed_imp(value, precision, &style)
+    }
+
+    /// Writes `value` like [`write_f64`](Self::write_f64), padded with `fill` to at least
+    /// `width` characters.
+    ///
+    /// The sign and decimal point count toward the width. Like `{:0N.P}`, a `'0'` fill with
+    /// right alignment goes between the sign and the digits, so `-1.5` becomes `-001.5`.
+    pub const fn write_f64_field(
+        &mut self,
+        value: f64,
+        precision: usize,
+        width: usize,
+        align: Align,
+        fill: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let decimal = match Decimal::from_f64(value) {
+            Some(mut decimal) => {
+                decimal.round(precision, RoundingMode::HalfEven);
+                Some(decimal)
+            }
+            None => None,
+        };
+
+        let (negative, body_len) = match &decimal {
+            Some(decimal) => (
+                decimal.negative,
+                decimal.fixed_len(precision, '.') - decimal.negative as usize,
+            ),
+            None => (!value.is_nan() && value.is_sign_negative(), 3),
+        };
+
+        let len = negative as usize + body_len;
+        let padding = Padding::new(len, len, width, align, fill);
+
+        if padding.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let sign_aware = fill == '0' && matches!(align, Align::Right);
+
+        unsafe {
+            if !sign_aware {
+                self.push_fill_before(&padding);
+            }
+
+            if negative {
+                self.push_byte_unchecked(b'-');
+            }
+
+            if sign_aware {
+                self.push_fill_before(&padding);
+            }
+
+            match decimal {
+                Some(mut decimal) => {
+                    decimal.negative = false;
+                    self.push_fixed_unchecked(&decimal, precision, '.', None);
+                }
+                None if value.is_nan() => self.push_str_unchecked("NaN"),
+                None => self.push_str_unchecked("inf"),
+            }
+
+            self.push_fill_after(&padding);
+        }
+
+        Ok(())
+    }
+
+    /// Like [`write_f64`](Self::write_f64), but rounding with `mode` instead of half to even.
+    pub const fn write_f64_rounded(
+        &mut self,
+        value: f64,
+        precision: usize,
+        mode: RoundingMode,
+    ) -> Result<(), BufferWriteFailed> {
+        let style = FixedStyle {
+            rounding: mode,
+            ..FixedStyle::DEFAULT
+        };
+
+        self.write_fixed_imp(value, precision, &style)
+    }
+
+    /// Like [`write_f64`](Self::write_f64), but dropping the digits past `precision` instead
+    /// of rounding them, so `1.999` at precision 2 is `1.99`. This is
+    /// [`RoundingMode::TowardZero`], so negative values are truncated toward zero as well.
+    pub const fn write_f64_trunc(
+        &mut self,
+        value: f64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_f64_rounded(value, precision, RoundingMode::TowardZero)
+    }
+
+    /// Like [`write_f64`](Self::write_f64), but with `point` as the decimal separator, so
+    /// `3.14` can be written as `3,14`.
+    pub const fn write_f64_with_sep(
+        &mut self,
+        value: f64,
+        precision: usize,
+        point: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let style = FixedStyle {
+            point,
+            ..FixedStyle::DEFAULT
+        };
+
+        self.write_fixed_imp(value, precision, &style)
+    }
+
+    /// Like [`write_f64`](Self::write_f64), but with `group_sep` between every three digits
+    /// of the integer part and `decimal_sep` as the decimal separator, so `1234567.5` at
+    /// precision 2 with `','` and `'.'` is `1,234,567.50`.
+    pub const fn write_f64_grouped(
+        &mut self,
+        value: f64,
+        precision: usize,
+        group_sep: char,
+        decimal_sep: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let style = FixedStyle {
+            point: decimal_sep,
+            group: Some(group_sep),
+            ..FixedStyle::DEFAULT
+        };
+
+        self.write_fixed_imp(value, precision, &style)
+    }
+
+    /// Writes `value` so that decimal points line up in a column: the sign and integer part
+    /// right-aligned in `int_width` characters, then the point and the fraction left-aligned
+    /// in `frac_width` more, both padded with `fill`.
+    ///
+    /// The fraction is rounded half to even to at most `frac_width` digits and trailing
+    /// zeros are dropped, so `1.5` with widths `3` and `2` is `"  1.5 "`. Integers have no
+    /// point, and fill takes its place too. An integer part wider than `int_width` is
+    /// written whole, which breaks the alignment. NaN and the infinities go in the integer
+    /// column.
+    pub const fn write_f64_point_aligned(
+        &mut self,
+        value: f64,
+        int_width: usize,
+        frac_width: usize,
+        fill: char,
+    ) -> Result<(), BufferWriteFailed> {
+        // the point column, filled if the value has no point
+        let frac_column = match frac_width {
+            0 => 0,
+            _ => frac_width.saturating_add(1),
+        };
+
+        let Some(mut decimal) = Decimal::from_f64(value) else {
+            let style = FixedStyle::DEFAULT;
+            let special = if value.is_nan() {
+                style.nan
+            } else if value.is_sign_negative() {
+                style.neg_inf
+            } else {
+                style.inf
+            };
+
+            let before = int_width.saturating_sub(special.len());
+            let padding = Padding::exact(special.len(), before, frac_column, fill);
+
+            if padding.len() > self.remaining_capacity() {
+                return Err(BufferWriteFailed);
+            }
+
+            unsafe {
+                self.push_fill_before(&padding);
+                self.push_str_unchecked(special);
+                self.push_fill_after(&padding);
+            }
+
+            return Ok(());
+        };
+
+        decimal.round(frac_width, RoundingMode::HalfEven);
+        decimal.trim();
+
+        let frac_digits = decimal.frac_digits;
+        let int_len = decimal.negative as usize + decimal.int_len();
+        let frac_len = match frac_digits {
+            0 => 0,
+            digits => digits + 1,
+        };
+
+        let before = int_width.saturating_sub(int_len);
+        let after = frac_column - frac_len;
+        let len = decimal.fixed_len(frac_digits, '.');
+        let padding = Padding::exact(len, before, after, fill);
+
+        if padding.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_fill_before(&padding);
+            self.push_fixed_unchecked(&decimal, frac_digits, '.', None);
+            self.push_fill_after(&padding);
+        }
+
+        Ok(())
+    }
+
+    /// Like [`write_f64`](Self::write_f64), but with trailing zeros and a dangling decimal
+    /// point removed, so `3.1400` is written as `3.14` and `3.0` as `3`.
+    ///
+    /// This writes the fewest fractional digits, up to `max_precision`, that show the value
+    /// rounded to `max_precision` digits: `0.5` is `0.5` and one third is `0.333` at 3.
+    /// The value is rounded first and only zeros are trimmed after, so trimming never
+    /// changes what is shown. A value that rounds to a whole number loses its fraction, so
+    /// `0.9999` at 3 is `1`, and `1e-10` at 3 is `0`.
+    pub const fn write_f64_trimmed(
+        &mut self,
+        value: f64,
+        max_precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        let style = FixedStyle {
+            trim: true,
+            ..FixedStyle::DEFAULT
+        };
+
+        self.write_fixed_imp(value, max_precision, &style)
+    }
+
+    /// Writes the IEEE 754 bits of `value` as `0x` and 16 lowercase hex digits, like
+    /// `{:#018x}` of [`f64::to_bits`], so `1.0` is `0x3ff0000000000000`.
+    ///
+    /// Unlike the decimal writers this is lossless for every value, including the sign of
+    /// zero and the payload of a NaN.
+    pub const fn write_f64_bits(&mut self, value: f64) -> Result<(), BufferWriteFailed> {
+        if "0x".len() + 16 > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_str_unchecked("0x");
+            self.push_hex_unchecked(value.to_bits(), 16, HEX_LOWER);
+        }
+
+        Ok(())
+    }
+
+    /// Writes the IEEE 754 fields of `value` in binary as `sign|exponent|mantissa`, with 1,
+    /// 11 and 52 digits, so `1.0` is `0|01111111111|` followed by 52 zeros.
+    pub const fn write_f64_bits_parts(&mut self, value: f64) -> Result<(), BufferWriteFailed> {
+        if 64 + "||".len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let bits = value.to_bits();
+        let mut i = 64;
+
+        while i > 0 {
+            i -= 1;
+
+            unsafe {
+                self.push_byte_unchecked(b'0' + (bits >> i & 1) as u8);
+
+                // after the sign and the exponent
+                if i == 63 || i == 52 {
+                    self.push_byte_unchecked(b'|');
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Like [`write_f64`](Self::write_f64), for an `f32`.
+    pub const fn write_f32(
+        &mut self,
+        value: f32,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_f64(value as f64, precision)
+    }
+
+    /// Writes `fraction` as a percentage with `precision` fractional digits, so `0.1234`
+    /// at precision 1 is `12.3%`.
+    ///
+    /// The scaling by 100 is exact, and rounding and special values are the same as for
+    /// [`write_f64`](Self::write_f64).
+    pub const fn write_percent(
+        &mut self,
+        fraction: f64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        let style = FixedStyle {
+            scale: 2,
+            suffix: "%",
+            ..FixedStyle::DEFAULT
+        };
+
+        self.write_fixed_imp(fraction, precision, &style)
+    }
+
+    /// Writes the amplitude ratio `ratio` in decibels, `20 * log10(ratio)`, with `precision`
+    /// fractional digits and a ` dB` suffix, so `0.5` at precision 2 is `-6.02 dB`.
+    ///
+    /// The logarithm is computed in `const` to within a few ulps of `f64::log10`. A zero
+    /// ratio is `-inf dB`, and negative or NaN ratios fail without writing anything.
+    pub const fn write_db(
+        &mut self,
+        ratio: f64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_db_imp(ratio, precision, 20.0)
+    }
+
+    /// Like [`write_db`](Self::write_db), but for a power ratio, `10 * log10(ratio)`, so `0.5`
+    /// is `-3.01 dB`.
+    pub const fn write_db_power(
+        &mut self,
+        ratio: f64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_db_imp(ratio, precision, 10.0)
+    }
+
+    const fn write_db_imp(
+        &mut self,
+        ratio: f64,
+        precision: usize,
+        factor: f64,
+    ) -> Result<(), BufferWriteFailed> {
+        // `-0.0` is a zero ratio too
+        let db = if ratio.is_nan() || ratio < 0.0 {
+            return Err(BufferWriteFailed);
+        } else if ratio == 0.0 {
+            f64::NEG_INFINITY
+        } else if ratio == f64::INFINITY {
+            f64::INFINITY
+        } else {
+            factor * log10(ratio)
+        };
+
+        let style = FixedStyle {
+            suffix: " dB",
+            ..FixedStyle::DEFAULT
+        };
+
+        self.write_fixed_imp(db, precision, &style)
+    }
+
+    /// Writes `value` with the fewest digits that read back as `value`, the same as `{:?}`.
+    ///
+    /// Magnitudes from `1e-4` up to `1e16` are written in fixed notation with at least one
+    /// fractional digit, like `0.001` or `12.0`, and the rest in scientific notation, like
+    /// `1e16` or `2.5e-7`. Zero is `0.0` or `-0.0`, NaN is `NaN`, and infinities are `inf`
+    /// and `-inf`.
+    pub const fn write_f64_auto(&mut self, value: f64) -> Result<(), BufferWriteFailed> {
+        if !value.is_finite() {
+            let special = if value.is_nan() {
+                "NaN"
+            } else if value.is_sign_negative() {
+                "-inf"
+            } else {
+                "inf"
+            };
+
+            return self.push_str(special);
+        }
+
+        let sign = value.is_sign_negative() as usize;
+        let magnitude = value.abs();
+
+        if magnitude == 0.0 {
+            return self.push_str(if sign == 1 { "-0.0" } else { "0.0" });
+        }
+
+        let (digits, exp) = shortest(magnitude);
+        let digit_count = u64_digit_count(digits);
+        let sci_exp = digit_count as isize - 1 + exp;
+
+        if 1e-4 <= magnitude && magnitude < 1e16 {
+            // below `1e16`, so the whole value fits in a `u64`
+            if exp >= 0 {
+                let int = digits * 10u64.pow(exp as u32);
+                let len = sign + u64_digit_count(int) + ".0".len();
+
+                if len > self.remaining_capacity() {
+                    return Err(BufferWriteFailed);
+                }
+
+                if sign == 1 {
+                    tri!(self.push_str("-"));
+                }
+
+                tri!(self.write_u64(int));
+                return self.push_str(".0");
+            }
+
+            let frac_len = exp.unsigned_abs();
+            let (int, frac) = match 10u64.checked_pow(frac_len as u32) {
+                Some(scale) => (digits / scale, digits % scale),
+                None => (0, digits),
+            };
+            let len = sign + u64_digit_count(int) + 1 + frac_len;
+
+            if len > self.remaining_capacity() {
+                return Err(BufferWriteFailed);
+            }
+
+            if sign == 1 {
+                tri!(self.push_str("-"));
+            }
+
+            tri!(self.write_u64(int));
+            tri!(self.push_str("."));
+            return self.write_u64_formatted(frac, frac_len, 0, '0');
+        }
+
+        let rest_len = digit_count - 1;
+        let point_len = (rest_len != 0) as usize;
+        let exp_len = (sci_exp < 0) as usize + u64_digit_count(sci_exp.unsigned_abs() as u64);
+        let len = sign + digit_count + point_len + "e".len() + exp_len;
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        if sign == 1 {
+            tri!(self.push_str("-"));
+        }
+
+        let scale = 10u64.pow(rest_len as u32);
+        tri!(self.write_u64(digits / scale));
+
+        if rest_len != 0 {
+            tri!(self.push_str("."));
+            tri!(self.write_u64_formatted(digits % scale, rest_len, 0, '0'));
+        }
+
+        tri!(self.push_str("e"));
+        self.write_i64(sci_exp as i64)
+    }
+
+    /// Writes `value` in engineering notation, like `12.3e3`: a mantissa with one to three
+    /// integer digits and `precision` fractional digits, and an exponent that is a multiple
+    /// of three.
+    ///
+    /// The mantissa is rounded half to even, moving to the next exponent if that reaches
+    /// `1000`. Zero is written with an exponent of `0`, and special values like
+    /// [`write_f64`](Self::write_f64) does.
+    pub const fn write_f64_eng(
+        &mut self,
+        value: f64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_exp_imp(value, precision, 3, '.', b'e')
+    }
+
+    /// Writes `value` rounded to `sig_figs` significant figures, without an exponent, so
+    /// `123.456` to 4 figures is `123.5` and `0.00123456` to 3 is `0.00123`.
+    ///
+    /// The last figure is rounded half to even, and trailing zeros are kept since they are
+    /// significant: `2.0` to 3 figures is `2.00`. Figures that fall in the integer part are
+    /// replaced by zeros, so `123456.0` to 2 is `120000`. Zero is written with
+    /// `sig_figs - 1` fractional digits, and special values like
+    /// [`write_f64`](Self::write_f64) does.
+    ///
+    /// # Panics
+    ///
+    /// If `sig_figs` is `0`.
+    pub const fn write_f64_sigfig(
+        &mut self,
+        value: f64,
+        sig_figs: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        assert!(sig_figs != 0, "sig_figs must be at least 1");
+
+        let Some(decimal) = Decimal::from_f64(value) else {
+            return self.write_f64(value, 0);
+        };
+
+        let mut lead = match decimal.leading_exp() {
+            Some(exp) => exp,
+            None => 0,
+        };
+
+        let (rounded, precision) = loop {
+            // the number of fractional digits that leaves `sig_figs` figures
+            let frac_digits = sig_figs as i128 - 1 - lead as i128;
+
+            // every figure is already there, the rest are zeros
+            if frac_digits >= decimal.frac_digits as i128 {
+                let precision = if frac_digits > usize::MAX as i128 {
+                    usize::MAX
+                } else {
+                    frac_digits as usize
+                };
+
+                break (decimal, precision);
+            }
+
+            let frac_digits = frac_digits as isize;
+            let mut rounded = decimal;
+            rounded.scale_pow10(frac_digits);
+            rounded.round(0, RoundingMode::HalfEven);
+
+            // rounding may carry into another figure, e.g. 9.96 to 2 figures
+            if rounded.digits.digit_count() > sig_figs {
+                lead += 1;
+            } else {
+                rounded.scale_pow10(-frac_digits);
+                break (
+                    rounded,
+                    if frac_digits > 0 {
+                        frac_digits as usize
+                    } else {
+                        0
+                    },
+                );
+            }
+        };
+
+        if rounded.fixed_len(precision, '.') > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_fixed_unchecked(&rounded, precision, '.', None) };
+
+        Ok(())
+    }
+
+    /// Writes `value` in scientific notation with `precision` fractional digits, like
+    /// `{:.Ne}`, so `12345.0` at precision 2 is `1.23e4`.
+    ///
+    /// The mantissa is rounded half to even like `std`, moving to the next exponent if that
+    /// reaches `10`. Zero is written with an exponent of `0`, and special values like
+    /// [`write_f64`](Self::write_f64) does.
+    pub const fn write_f64_exp(
+        &mut self,
+        value: f64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_exp_imp(value, precision, 1, '.', b'e')
+    }
+
+    /// Like [`write_f64_exp`](Self::write_f64_exp), but with an uppercase `E`, like
+    /// `{:.NE}`.
+    pub const fn write_f64_exp_upper(
+        &mut self,
+        value: f64,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_exp_imp(value, precision, 1, '.', b'E')
+    }
+
+    /// Like [`write_f64_exp`](Self::write_f64_exp), but with `point` as the decimal
+    /// separator of the mantissa, so `1.23e4` can be written as `1,23e4`.
+    pub const fn write_f64_exp_with_sep(
+        &mut self,
+        value: f64,
+        precision: usize,
+        point: char,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_exp_imp(value, precision, 1, point, b'e')
+    }
+
+    /// Like [`write_f64_exp_upper`](Self::write_f64_exp_upper), but with `point` as the
+    /// decimal separator of the mantissa.
+    pub const fn write_f64_exp_upper_with_sep(
+        &mut self,
+        value: f64,
+        precision: usize,
+        point: char,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_exp_imp(value, precision, 1, point, b'E')
+    }
+
+    // writes `value` as a mantissa with one to `step` integer digits, `e`, and an exponent that
+    // is a multiple of `step`
+    const fn write_exp_imp(
+        &mut self,
+        value: f64,
+        precision: usize,
+        step: isize,
+        point: char,
+        e: u8,
+    ) -> Result<(), BufferWriteFailed> {
+        let Some(decimal) = Decimal::from_f64(value) else {
+            return self.write_f64(value, precision);
+        };
+
+        let mut exp = match decimal.leading_exp() {
+            Some(exp) => exp.div_euclid(step) * step,
+            None => 0,
+        };
+
+        let mantissa = loop {
+            let mut mantissa = decimal;
+            mantissa.scale_pow10(-exp);
+            mantissa.round(precision, RoundingMode::HalfEven);
+
+            // rounding may carry into the next exponent, e.g. 999.96 at precision 1
+            if mantissa.int_len() > step as usize {
+                exp += step;
+            } else {
+                break mantissa;
+            }
+        };
+
+        let exp_len = (exp < 0) as usize + u64_digit_count(exp.unsigned_abs() as u64);
+        let len = mantissa
+            .fixed_len(precision, point)
+            .saturating_add(1 + exp_len);
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_fixed_unchecked(&mantissa, precision, point, None);
+            self.push_byte_unchecked(e);
+        }
+
+        // can't fail, the room was checked above
+        let _ = self.write_i64(exp as i64);
+
+        Ok(())
+    }
+
+    /// Writes `value` scaled to an SI prefix from `n` to `G`, with `precision` fractional
+    /// digits, a space, the prefix, and `unit`: `1500.0` with unit `Hz` is `1.50 kHz` at
+    /// precision 2.
+    ///
+    /// The prefix is picked so that the rounded number is at least `1` and below `1000`,
+    /// unless the value is out of the range of the prefixes. Zero has no prefix, and special
+    /// values are written like [`write_f64`](Self::write_f64) does, followed by the unit.
+    pub const fn write_metric(
+        &mut self,
+        value: f64,
+        unit: &str,
+        precision: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        const PREFIXES: [&str; 7] = ["n", "µ", "m", "", "k", "M", "G"];
+        const NO_PREFIX: isize = 3;
+
+        let Some(decimal) = Decimal::from_f64(value) else {
+            let style = FixedStyle::DEFAULT;
+            let special = if value.is_nan() {
+                style.nan
+            } else if value.is_sign_negative() {
+                style.neg_inf
+            } else {
+                style.inf
+            };
+
+            if (special.len() + 1).saturating_add(unit.len()) > self.remaining_capacity() {
+                return Err(BufferWriteFailed);
+            }
+
+            tri!(self.push_str(special));
+            tri!(self.push_str(" "));
+            return self.push_str(unit);
+        };
+
+        let mut group = match decimal.leading_exp() {
+            None => NO_PREFIX,
+            Some(exp) => {
+                let group = exp.div_euclid(3) + NO_PREFIX;
+
+                if group < 0 {
+                    0
+                } else if group >= PREFIXES.len() as isize {
+                    PREFIXES.len() as isize - 1
+                } else {
+                    group
+                }
+            }
+        };
+
+        let scaled = loop {
+            let mut scaled = decimal;
+            scaled.scale_pow10(-3 * (group - NO_PREFIX));
+            scaled.round(precision, RoundingMode::HalfEven);
+
+            // rounding may carry into the next prefix, e.g. 999.996 Hz
+            if scaled.int_len() > 3 && group + 1 < PREFIXES.len() as isize {
+                group += 1;
+            } else {
+                break scaled;
+            }
+        };
+
+        let prefix = PREFIXES[group as usize];
+        let len = scaled
+            .fixed_len(precision, '.')
+            .saturating_add(1 + prefix.len())
+            .saturating_add(unit.len());
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_fixed_unchecked(&scaled, precision, '.', None);
+            self.push_byte_unchecked(b' ');
+            self.push_str_unchecked(prefix);
+            self.push_str_unchecked(unit);
+        }
+
+        Ok(())
+    }
+
+    const fn write_fixed_imp(
+        &mut self,
+        value: f64,
+        precision: usize,
+        style: &FixedStyle<'_>,
+    ) -> Result<(), BufferWriteFailed> {
+        let suffix = style.suffix;
+
+        let Some(mut decimal) = Decimal::from_f64(value) else {
+            let special = if value.is_nan() {
+                style.nan
+            } else if value.is_sign_negative() {
+                style.neg_inf
+            } else {
+                style.inf
+            };
+
+            if special.len().saturating_add(suffix.len()) > self.remaining_capacity() {
+                return Err(BufferWriteFailed);
+            }
+
+            tri!(self.push_str(special));
+            return self.push_str(suffix);
+        };
+
+        decimal.mul_pow10(style.scale);
+        decimal.round(precision, style.rounding);
+
+        let precision = if style.trim {
+            decimal.trim();
+            decimal.frac_digits
+        } else {
+            precision
+        };
+
+        if decimal
+            .fixed_len(precision, style.point)
+            .saturating_add(decimal.group_len(style.group) + suffix.len())
+            > self.remaining_capacity()
+        {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_fixed_unchecked(&decimal, precision, style.point, style.group);
+            self.push_str_unchecked(suffix);
+        }
+
+        Ok(())
+    }
+
+    // `decimal` must have at most `precision` fractional digits, and there must be room
+    // for `decimal.fixed_len(precision, point) + decimal.group_len(group)` bytes
+    const unsafe fn push_fixed_unchecked(
+        &mut self,
+        decimal: &Decimal,
+        precision: usize,
+        point: char,
+        group: Option<char>,
+    ) {
+        let frac_digits = decimal.frac_digits;
+        let int_len = decimal.int_len();
+
+        unsafe {
+            if decimal.negative {
+                self.push_byte_unchecked(b'-');
+            }
+
+            let mut i = int_len;
+
+            while i > 0 {
+                i -= 1;
+                self.push_byte_unchecked(b'0' + decimal.digits.digit(frac_digits + i));
+
+                if let Some(sep) = group
+                    && i != 0
+                    && i.is_multiple_of(3)
+                {
+                    // can't fail, the caller made room
+                    let _ = self.write_char(sep);
+                }
+            }
+
+            if precision != 0 {
+                // can't fail, the caller made room
+                let _ = self.write_char(point);
+            }
+
+            let mut i = 0;
+
+            while i < precision {
+                let digit = if i < frac_digits {
+                    decimal.digits.digit(frac_digits - 1 - i)
+                } else {
+                    0
+                };
+
+                self.push_byte_unchecked(b'0' + digit);
+                i += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn test_floats() -> impl Iterator<Item = f64> {
+    let random = crate::test_rng::random_u64s(0x243f_6a88_85a3_08d3)
+        .map(f64::from_bits)
+        .filter(|value| value.is_finite())
+        .take(2000);
+
+    let fixed = [
+        0.0,
+        -0.0,
+        0.5,
+        1.5,
+        2.5,
+        -2.5,
+        0.125,
+        0.375,
+        1e-7,
+        0.1,
+        0.3,
+        2.675,
+        999.996,
+        9.5,
+        123456.789,
+        1e15,
+        1e22,
+        1e23,
+        f64::MAX,
+        f64::MIN,
+        f64::MIN_POSITIVE,
+        f64::EPSILON,
+        5e-324,
+        -5e-324,
+    ];
+
+    fixed.into_iter().chain(random)
+}
+
+#[test]
+fn test_f64() {
+    let mut buffer = Buffer::new::<1500>();
+
+    for value in test_floats() {
+        for precision in [0, 1, 2, 3, 6, 17, 30, 400, 1100] {
+            buffer.clear();
+            buffer.write_f64(value, precision).unwrap();
+            assert_eq!(buffer.as_str(), format!("{value:.precision$}"), "{value:e}");
+        }
+    }
+
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        buffer.clear();
+        buffer.write_f64(value, 3).unwrap();
+        assert_eq!(buffer.as_str(), format!("{value:.3}"));
+    }
+
+    for (value, precision, expected) in [
+        (-0.0, 2, "-0.00"),
+        (0.0, 2, "0.00"),
+        (-0.0, 0, "-0"),
+        (-0.001, 2, "-0.00"),
+        (-0.4, 0, "-0"),
+    ] {
+        buffer.clear();
+        buffer.write_f64(value, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+}
+
+#[test]
+fn test_f64_trimmed() {
+    let mut buffer = Buffer::new::<1500>();
+
+    let cases: &[(f64, usize, &str)] = &[
+        (1.0, 4, "1"),
+        (1.10, 4, "1.1"),
+        (0.0, 4, "0"),
+        (-0.0, 2, "-0"),
+        (3.25, 4, "3.25"),
+        (3.1251, 2, "3.13"),
+        (2.5, 0, "2"),
+        (10.0, 3, "10"),
+        (0.999, 2, "1"),
+        (1e-10, 4, "0"),
+        (1.0 / 3.0, 3, "0.333"),
+        (0.5, 3, "0.5"),
+        (0.9999, 3, "1"),
+        (-0.9999, 3, "-1"),
+        (0.1 + 0.2, 3, "0.3"),
+        (
+            0.1,
+            usize::MAX,
+            "0.1000000000000000055511151231257827021181583404541015625",
+        ),
+        (f64::NAN, 3, "NaN"),
+    ];
+
+    for &(value, max_precision, expected) in cases {
+        buffer.clear();
+        buffer.write_f64_trimmed(value, max_precision).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    for value in test_floats() {
+        for precision in [0, 3, 17, 1100] {
+            let fixed = format!("{value:.precision$}");
+            let expected = match fixed.contains('.') {
+                true => fixed.trim_end_matches('0').trim_end_matches('.'),
+                false => &fixed,
+            };
+
+            buffer.clear();
+            buffer.write_f64_trimmed(value, precision).unwrap();
+            assert_eq!(buffer.as_str(), expected, "{value:e}");
+        }
+    }
+
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_f64_trimmed(1.25, 4).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_with_sep() {
+    let mut buffer = Buffer::new::<32>();
+    buffer.write_f64_with_sep(1.23456, 2, ',').unwrap();
+    assert_eq!(buffer.as_str(), "1,23");
+
+    buffer.clear();
+    buffer.write_f64_with_sep(-0.5, 0, ',').unwrap();
+    assert_eq!(buffer.as_str(), "-0");
+
+    buffer.clear();
+    buffer.write_f64_with_sep(1.5, 3, '·').unwrap();
+    assert_eq!(buffer.as_str(), "1·500");
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_f64_with_sep(1.5, 3, '·').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_grouped() {
+    let cases: &[(f64, usize, char, char, &str)] = &[
+        (1234567.5, 2, ',', '.', "1,234,567.50"),
+        (-1234567.5, 0, ',', '.', "-1,234,568"),
+        (999.999, 2, ',', '.', "1,000.00"),
+        (123.0, 1, ',', '.', "123.0"),
+        (-0.5, 1, ',', '.', "-0.5"),
+        (1234.5678, 3, '.', ',', "1.234,568"),
+        (12345678.0, 0, '\u{202f}', '.', "12\u{202f}345\u{202f}678"),
+        (f64::NEG_INFINITY, 2, ',', '.', "-inf"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for &(value, precision, group_sep, decimal_sep, expected) in cases {
+        buffer.clear();
+        buffer
+            .write_f64_grouped(value, precision, group_sep, decimal_sep)
+            .unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    for value in test_floats() {
+        if value.abs() > 1e20 {
+            continue;
+        }
+
+        buffer.clear();
+        buffer.write_f64_grouped(value, 3, '_', '.').unwrap();
+        assert_eq!(buffer.as_str().replace('_', ""), format!("{value:.3}"));
+    }
+
+    // 9 bytes without the separator
+    let mut buffer = Buffer::new::<9>();
+    assert!(buffer.write_f64_grouped(-1234.5, 3, ',', '.').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_with() {
+    let mut buffer = Buffer::new::<16>();
+    let cases = [
+        (f64::NAN, "nan"),
+        (f64::INFINITY, "+Inf"),
+        (f64::NEG_INFINITY, "-Inf"),
+        (1.25, "1.2"),
+    ];
+
+    for (value, expected) in cases {
+        buffer.clear();
+        buffer
+            .write_f64_with(value, 1, "nan", "+Inf", "-Inf")
+            .unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<3>();
+    assert!(
+        buffer
+            .write_f64_with(f64::INFINITY, 1, "nan", "+Inf", "-Inf")
+            .is_err()
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_field() {
+    let mut buffer = Buffer::new::<64>();
+    let values = [
+        0.0,
+        -0.0,
+        1.5,
+        -1.25,
+        123.456,
+        -9.99,
+        1e10,
+        f64::NAN,
+        f64::NEG_INFINITY,
+    ];
+
+    for value in values {
+        for precision in [0, 2] {
+            for width in [0, 3, 8, 12] {
+                let cases = [
+                    (Align::Right, ' ', format!("{value:>width$.precision$}")),
+                    (Align::Left, ' ', format!("{value:<width$.precision$}")),
+                    (Align::Center, '*', format!("{value:*^width$.precision$}")),
+                    (Align::Right, '0', format!("{value:0width$.precision$}")),
+                ];
+
+                for (align, fill, expected) in cases {
+                    buffer.clear();
+                    buffer
+                        .write_f64_field(value, precision, width, align, fill)
+                        .unwrap();
+                    assert_eq!(
+                        buffer.as_str(),
+                        expected,
+                        "{value} {width} {align:?} {fill:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(
+        buffer
+            .write_f64_field(-1.5, 1, 6, Align::Right, '0')
+            .is_err()
+    );
+    assert!(
+        buffer
+            .write_f64_field(1.0, 0, usize::MAX, Align::Left, ' ')
+            .is_err()
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_rounded() {
+    use RoundingMode::*;
+
+    // 0.125 and 0.375 are exact ties, 0.1 and 0.3 are just above and below them
+    let cases: &[(f64, [&str; 5])] = &[
+        (0.125, ["0.12", "0.13", "0.12", "0.12", "0.13"]),
+        (0.375, ["0.38", "0.38", "0.37", "0.37", "0.38"]),
+        (-0.125, ["-0.12", "-0.13", "-0.12", "-0.12", "-0.13"]),
+        (2.5, ["2.50", "2.50", "2.50", "2.50", "2.50"]),
+        (0.1, ["0.10", "0.10", "0.10", "0.10", "0.11"]),
+        (0.3, ["0.30", "0.30", "0.30", "0.29", "0.30"]),
+        (0.999, ["1.00", "1.00", "1.00", "0.99", "1.00"]),
+    ];
+
+    let mut buffer = Buffer::new::<16>();
+
+    for &(value, expected) in cases {
+        for (mode, expected) in [HalfEven, HalfUp, HalfDown, TowardZero, AwayFromZero]
+            .into_iter()
+            .zip(expected)
+        {
+            buffer.clear();
+            buffer.write_f64_rounded(value, 2, mode).unwrap();
+            assert_eq!(buffer.as_str(), expected, "{value} {mode:?}");
+        }
+    }
+
+    for value in [0.5, 1.5, 2.5, -2.5] {
+        let [even, up, down] = [HalfEven, HalfUp, HalfDown].map(|mode| {
+            buffer.clear();
+            buffer.write_f64_rounded(value, 0, mode).unwrap();
+            buffer.as_str().to_string()
+        });
+
+        assert_eq!(even, format!("{value:.0}"));
+        assert_eq!(up, format!("{:.0}", value.round()));
+        assert_eq!(down, format!("{:.0}", value.trunc()));
+    }
+
+    assert_eq!(RoundingMode::default(), HalfEven);
+}
+
+#[test]
+fn test_f64_trunc() {
+    let cases = [
+        (1.999, 2, "1.99"),
+        (0.9999, 3, "0.999"),
+        (0.9999, 0, "0"),
+        (-0.9999, 2, "-0.99"),
+        (-1.999, 0, "-1"),
+        (-0.001, 2, "-0.00"),
+        (2.5, 0, "2"),
+        (1.25, 2, "1.25"),
+        // 0.3 is just below its decimal value, and 0.1 just above
+        (0.3, 1, "0.2"),
+        (0.1, 1, "0.1"),
+        (f64::MAX, 0, &format!("{:.0}", f64::MAX)),
+        (-f64::INFINITY, 2, "-inf"),
+    ];
+
+    let mut buffer = Buffer::new::<320>();
+
+    for (value, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_f64_trunc(value, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{value} {precision}");
+    }
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_f64_trunc(-1.999, 2).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_bits() {
+    let values = [
+        0.0,
+        -0.0,
+        1.0,
+        -2.5,
+        0.1,
+        5e-324,
+        f64::MAX,
+        f64::INFINITY,
+        f64::NAN,
+        f64::from_bits(0x7ff0_0000_0000_0001),
+    ];
+
+    let mut buffer = Buffer::new::<66>();
+
+    for value in values.into_iter().chain(test_floats()) {
+        let bits = value.to_bits();
+
+        buffer.clear();
+        buffer.write_f64_bits(value).unwrap();
+        assert_eq!(buffer.as_str(), format!("{bits:#018x}"));
+        assert_eq!(
+            u64::from_str_radix(&buffer[2..], 16)
+                .map(f64::from_bits)
+                .unwrap()
+                .to_bits(),
+            bits
+        );
+
+        buffer.clear();
+        buffer.write_f64_bits_parts(value).unwrap();
+        let binary = format!("{bits:064b}");
+        let expected = format!("{}|{}|{}", &binary[..1], &binary[1..12], &binary[12..]);
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    buffer.clear();
+    buffer.write_f64_bits(1.0).unwrap();
+    assert_eq!(buffer.as_str(), "0x3ff0000000000000");
+
+    buffer.clear();
+    buffer.write_f64_bits_parts(-2.0).unwrap();
+    assert_eq!(buffer.as_str(), format!("1|10000000000|{}", "0".repeat(52)));
+
+    let mut buffer = Buffer::new::<65>();
+    assert!(buffer.write_f64_bits_parts(1.0).is_err());
+    buffer.push_str(&"x".repeat(48)).unwrap();
+    assert!(buffer.write_f64_bits(1.0).is_err());
+    assert_eq!(buffer.len(), 48);
+}
+
+#[test]
+fn test_integer_and_fraction_parts() {
+    assert_eq!(f64_integer_part(0.0), Some(0));
+    assert_eq!(f64_integer_part(-3.7), Some(3));
+    assert_eq!(f64_integer_part(1e19), Some(10_000_000_000_000_000_000));
+    assert_eq!(
+        f64_integer_part(18446744073709549568.0),
+        Some(18446744073709549568)
+    );
+    assert_eq!(f64_integer_part(18446744073709551616.0), None);
+    assert_eq!(f64_integer_part(f64::NAN), None);
+    assert_eq!(f64_integer_part(f64::INFINITY), None);
+
+    assert_eq!(f64_fraction_digits(1.25, 1), Some(2));
+    assert_eq!(f64_fraction_digits(1.25, 4), Some(2500));
+    assert_eq!(f64_fraction_digits(-0.999, 2), Some(99));
+    assert_eq!(
+        f64_fraction_digits(0.1, 19),
+        Some(1_000_000_000_000_000_055)
+    );
+    assert_eq!(f64_fraction_digits(7.0, 0), Some(0));
+    assert_eq!(f64_fraction_digits(0.5, 20), None);
+    assert_eq!(f64_fraction_digits(f64::NEG_INFINITY, 2), None);
+
+    for value in test_floats() {
+        let truncated = value.abs().trunc();
+
+        if truncated < 18446744073709551616.0 {
+            assert_eq!(f64_integer_part(value), Some(truncated as u64), "{value:e}");
+        } else {
+            assert_eq!(f64_integer_part(value), None, "{value:e}");
+        }
+
+        // every f64 has fewer than 1100 fractional digits, so this doesn't round
+        let exact = format!("{:.1100}", value.abs());
+        let (_, frac) = exact.split_once('.').unwrap();
+        let expected = frac[..6].parse().ok();
+        assert_eq!(f64_fraction_digits(value, 6), expected, "{value:e}");
+    }
+
+    const PARTS: (Option<u64>, Option<u64>) = (f64_integer_part(2.5), f64_fraction_digits(2.5, 1));
+    assert_eq!(PARTS, (Some(2), Some(5)));
+}
+
+#[cfg(test)]
+fn reference_eng(value: f64, precision: usize) -> String {
+    if value == 0.0 {
+        return format!("{value:.precision$}e0");
+    }
+
+    // std's scientific notation with as many more digits as the mantissa has integer
+    // digits past the first, so the digits are the same
+    let shortest = format!("{value:e}");
+    let exp: i32 = shortest.split_once('e').unwrap().1.parse().unwrap();
+    let extra = exp.rem_euclid(3) as usize;
+    let sci = format!("{:.*e}", precision + extra, value.abs());
+    let (mantissa, rounded_exp) = sci.split_once('e').unwrap();
+    let rounded_exp: i32 = rounded_exp.parse().unwrap();
+
+    // if rounding carried into the next power of ten, the digits past this are all zeros
+    let mut digits: String = mantissa.chars().filter(|&c| c != '.').collect();
+    let eng_exp = rounded_exp.div_euclid(3) * 3;
+    let int_digits = (rounded_exp - eng_exp) as usize + 1;
+    digits.extend(core::iter::repeat_n('0', 3));
+    digits.truncate(int_digits + precision);
+
+    let sign = if value < 0.0 { "-" } else { "" };
+    let (int, frac) = digits.split_at(int_digits);
+
+    match precision {
+        0 => format!("{sign}{int}e{eng_exp}"),
+        _ => format!("{sign}{int}.{frac}e{eng_exp}"),
+    }
+}
+
+#[test]
+fn test_f64_eng() {
+    let cases: &[(f64, usize, &str)] = &[
+        (12_300.0, 1, "12.3e3"),
+        (1.0, 2, "1.00e0"),
+        (999.0, 0, "999e0"),
+        (999.96, 1, "1.0e3"),
+        (1000.0, 0, "1e3"),
+        (0.001_5, 2, "1.50e-3"),
+        (0.000_123, 1, "123.0e-6"),
+        (-4.7e-9, 1, "-4.7e-9"),
+        (0.0, 1, "0.0e0"),
+        (-0.0, 0, "-0e0"),
+        (f64::MAX, 3, "179.769e306"),
+        (5e-324, 2, "4.94e-324"),
+        (f64::NAN, 2, "NaN"),
+        (f64::INFINITY, 2, "inf"),
+    ];
+
+    let mut buffer = Buffer::new::<64>();
+
+    for &(value, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_f64_eng(value, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{value:e}");
+
+        if value.is_finite() {
+            assert_eq!(reference_eng(value, precision), expected, "{value:e}");
+        }
+    }
+
+    for value in test_floats() {
+        for precision in [0, 1, 3, 10] {
+            buffer.clear();
+            buffer.write_f64_eng(value, precision).unwrap();
+            assert_eq!(
+                buffer.as_str(),
+                reference_eng(value, precision),
+                "{value:e}"
+            );
+        }
+    }
+
+    let mut buffer = Buffer::new::<6>();
+    assert!(buffer.write_f64_eng(0.001_5, 2).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_sigfig() {
+    // places the decimal point in the digits of `{:.Ne}`
+    fn reference(value: f64, sig_figs: usize) -> String {
+        let exp = format!("{:.*e}", sig_figs - 1, value.abs());
+        let (mantissa, exp) = exp.split_once('e').unwrap();
+        let digits = mantissa.replace('.', "");
+        let exp: isize = exp.parse().unwrap();
+        let sign = if value.is_sign_negative() { "-" } else { "" };
+
+        if value == 0.0 {
+            return format!("{sign}{:.*}", sig_figs - 1, 0.0);
+        }
+
+        let int_len = exp + 1;
+
+        if int_len <= 0 {
+            format!("{sign}0.{}{digits}", "0".repeat(-int_len as usize))
+        } else if int_len as usize >= digits.len() {
+            format!(
+                "{sign}{digits}{}",
+                "0".repeat(int_len as usize - digits.len())
+            )
+        } else {
+            let (int, frac) = digits.split_at(int_len as usize);
+            format!("{sign}{int}.{frac}")
+        }
+    }
+
+    let cases: &[(f64, usize, &str)] = &[
+        (123.456, 4, "123.5"),
+        (0.001_234_56, 3, "0.00123"),
+        (123_456.0, 2, "120000"),
+        (2.0, 3, "2.00"),
+        (9.96, 2, "10"),
+        (0.0996, 2, "0.10"),
+        (99_960.0, 3, "100000"),
+        (2.5, 1, "2"),
+        (3.5, 1, "4"),
+        (-0.000_45, 1, "-0.0004"),
+        (0.0, 3, "0.00"),
+        (-0.0, 1, "-0"),
+        (1.0, 1, "1"),
+        (f64::NAN, 3, "NaN"),
+        (f64::NEG_INFINITY, 3, "-inf"),
+    ];
+
+    let mut buffer = Buffer::new::<1200>();
+
+    for &(value, sig_figs, expected) in cases {
+        buffer.clear();
+        buffer.write_f64_sigfig(value, sig_figs).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{value:e} {sig_figs}");
+
+        if value.is_finite() {
+            assert_eq!(reference(value, sig_figs), expected, "{value:e} {sig_figs}");
+        }
+    }
+
+    for value in test_floats() {
+        for sig_figs in [1, 2, 3, 6, 17, 30] {
+            buffer.clear();
+            buffer.write_f64_sigfig(value, sig_figs).unwrap();
+            assert_eq!(
+                buffer.as_str(),
+                reference(value, sig_figs),
+                "{value:e} {sig_figs}"
+            );
+        }
+    }
+
+    // the smallest subnormal has 751 figures, any more are zeros
+    buffer.clear();
+    buffer.write_f64_sigfig(5e-324, 800).unwrap();
+    assert_eq!(buffer.as_str(), format!("{:.1123}", 5e-324));
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_f64_sigfig(0.001_234, 2).is_err());
+    assert!(buffer.write_f64_sigfig(1.0, usize::MAX).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic = "at least 1"]
+fn test_f64_sigfig_zero_figures() {
+    let _ = Buffer::new::<8>().write_f64_sigfig(1.0, 0);
+}
+
+#[test]
+fn test_f64_point_aligned() {
+    let cases: &[(f64, usize, usize, char, &str)] = &[
+        (1.5, 3, 2, ' ', "  1.5 "),
+        (12.25, 3, 2, ' ', " 12.25"),
+        (100.0, 3, 2, ' ', "100   "),
+        (-3.125, 3, 2, ' ', " -3.12"),
+        (0.999, 3, 2, ' ', "  1   "),
+        (-0.0, 3, 1, '_', "_-0__"),
+        (12_345.5, 3, 1, ' ', "12345.5"),
+        (2.5, 0, 0, ' ', "2"),
+        (7.0, 2, 0, '.', ".7"),
+        (f64::NAN, 4, 2, ' ', " NaN   "),
+        (f64::NEG_INFINITY, 3, 0, ' ', "-inf"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for &(value, int_width, frac_width, fill, expected) in cases {
+        buffer.clear();
+        buffer
+            .write_f64_point_aligned(value, int_width, frac_width, fill)
+            .unwrap();
+        assert_eq!(buffer.as_str(), expected, "{value}");
+    }
+
+    // every point in a column lines up, at the same total width
+    let column = [0.5, -17.25, 3.0, 1234.125, -0.001];
+
+    for value in column {
+        buffer.clear();
+        buffer.write_f64_point_aligned(value, 5, 3, ' ').unwrap();
+        assert_eq!(buffer.len(), 9, "{value}");
+
+        match buffer.find(".") {
+            Some(point) => assert_eq!(point, 5, "{value}"),
+            None => assert_eq!(&buffer[5..], "    ", "{value}"),
+        }
+    }
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_f64_point_aligned(1.5, 3, 2, ' ').is_err());
+    assert!(
+        buffer
+            .write_f64_point_aligned(1.5, usize::MAX, 2, ' ')
+            .is_err()
+    );
+    assert!(
+        buffer
+            .write_f64_point_aligned(1.0, 1, usize::MAX, ' ')
+            .is_err()
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_exp() {
+    let cases: &[(f64, usize, &str)] = &[
+        (12_345.0, 2, "1.23e4"),
+        (25.0, 0, "2e1"),
+        (35.0, 0, "4e1"),
+        (9.96, 1, "1.0e1"),
+        (0.000_123, 1, "1.2e-4"),
+        (0.0, 2, "0.00e0"),
+        (-0.0, 0, "-0e0"),
+        (f64::MAX, 3, "1.798e308"),
+        (5e-324, 2, "4.94e-324"),
+        (f64::NAN, 2, "NaN"),
+        (f64::NEG_INFINITY, 2, "-inf"),
+    ];
+
+    let mut buffer = Buffer::new::<64>();
+
+    for &(value, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_f64_exp(value, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{value:e}");
+    }
+
+    for value in test_floats() {
+        for precision in [0, 1, 2, 5, 16, 20] {
+            buffer.clear();
+            buffer.write_f64_exp(value, precision).unwrap();
+            assert_eq!(buffer.as_str(), format!("{value:.precision$e}"));
+
+            buffer.clear();
+            buffer.write_f64_exp_upper(value, precision).unwrap();
+            assert_eq!(buffer.as_str(), format!("{value:.precision$E}"));
+        }
+    }
+
+    buffer.clear();
+    buffer.write_f64_exp_with_sep(-12_345.0, 2, ',').unwrap();
+    assert_eq!(buffer.as_str(), "-1,23e4");
+
+    buffer.clear();
+    buffer
+        .write_f64_exp_upper_with_sep(0.000_123, 1, '·')
+        .unwrap();
+    assert_eq!(buffer.as_str(), "1·2E-4");
+
+    // the separator is left out without fractional digits
+    buffer.clear();
+    buffer.write_f64_exp_with_sep(5.0, 0, ',').unwrap();
+    assert_eq!(buffer.as_str(), "5e0");
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_f64_exp_upper(0.001_5, 2).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_metric() {
+    let cases: &[(f64, usize, &str)] = &[
+        (1500.0, 2, "1.50 kHz"),
+        (1000.0, 1, "1.0 kHz"),
+        (999.0, 1, "999.0 Hz"),
+        (999.96, 1, "1.0 kHz"),
+        (1.0, 0, "1 Hz"),
+        (0.0, 2, "0.00 Hz"),
+        (-0.0, 0, "-0 Hz"),
+        (0.001, 3, "1.000 mHz"),
+        (0.000_000_5, 1, "500.0 nHz"),
+        (2.5e-6, 2, "2.50 µHz"),
+        (-4.2e9, 1, "-4.2 GHz"),
+        (1e-12, 3, "0.001 nHz"),
+        (5e12, 0, "5000 GHz"),
+        (0.000_999_999, 2, "1.00 mHz"),
+        (f64::NAN, 2, "NaN Hz"),
+        (f64::NEG_INFINITY, 2, "-inf Hz"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for &(value, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_metric(value, "Hz", precision).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{value:e}");
+    }
+
+    let mut buffer = Buffer::new::<8>();
+    assert!(buffer.write_metric(2.5e-6, "Hz", 2).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f32() {
+    let mut buffer = Buffer::new::<200>();
+
+    for value in [0.1f32, -2.5, 16777217.0, f32::MAX, f32::MIN_POSITIVE, 1e-45] {
+        for precision in [0, 2, 9, 60] {
+            buffer.clear();
+            buffer.write_f32(value, precision).unwrap();
+            assert_eq!(buffer.as_str(), format!("{value:.precision$}"));
+        }
+    }
+}
+
+#[test]
+fn test_f64_capacity() {
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_f64(-1.005, 3).is_err());
+    assert!(buffer.write_f64(99.995, 2).is_err());
+    assert!(buffer.is_empty());
+
+    buffer.write_f64(99.994, 2).unwrap();
+    assert_eq!(buffer.as_str(), "99.99");
+
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_f64(f64::NEG_INFINITY, 0).is_err());
+    assert!(buffer.write_f64(0.0, usize::MAX).is_err());
+
+    const {
+        let mut buffer = Buffer::new::<8>();
+        assert!(buffer.write_f64(-1.255, 2).is_ok());
+        assert!(buffer.ends_with("-1.25"));
+    }
+}
+
+#[test]
+fn test_log10() {
+    let cases = [
+        1.0,
+        10.0,
+        0.1,
+        2.0,
+        0.5,
+        1e-300,
+        1e300,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        5e-324,
+    ];
+
+    for x in cases.into_iter().chain(test_floats().map(f64::abs)) {
+        if x == 0.0 || !x.is_finite() {
+            continue;
+        }
+
+        let expected = x.log10();
+        let error = (log10(x) - expected).abs();
+        assert!(error <= 1e-15 * expected.abs().max(1.0), "{x:e}: {error:e}");
+    }
+
+    assert_eq!(log10(1.0), 0.0);
+}
+
+#[test]
+fn test_db() {
+    let cases: &[(f64, usize, &str)] = &[
+        (0.5, 2, "-6.02 dB"),
+        (2.0, 2, "6.02 dB"),
+        (10.0, 0, "20 dB"),
+        (1.0, 1, "0.0 dB"),
+        (1e-5, 3, "-100.000 dB"),
+        (0.0, 2, "-inf dB"),
+        (-0.0, 2, "-inf dB"),
+        (f64::INFINITY, 2, "inf dB"),
+    ];
+
+    let mut buffer = Buffer::new::<16>();
+
+    for &(ratio, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_db(ratio, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    buffer.clear();
+    buffer.write_db_power(0.5, 2).unwrap();
+    assert_eq!(buffer.as_str(), "-3.01 dB");
+
+    buffer.clear();
+    buffer.write_db_power(100.0, 1).unwrap();
+    assert_eq!(buffer.as_str(), "20.0 dB");
+
+    buffer.clear();
+    assert!(buffer.write_db(-1.0, 2).is_err());
+    assert!(buffer.write_db(f64::NAN, 2).is_err());
+    assert!(buffer.is_empty());
+
+    let mut buffer = Buffer::new::<7>();
+    assert!(buffer.write_db(0.5, 2).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_f64_auto() {
+    let cases = [
+        1.0,
+        0.1,
+        1e-4,
+        9.9999e-5,
+        1e16,
+        9999999999999998.0,
+        1e21,
+        123456.789,
+        2.5e-7,
+        5e-324,
+        f64::MAX,
+        1.0 / 3.0,
+        // -1186556465612211.25, where `.2` and `.3` are equally close and `std` picks `.3`
+        f64::from_bits(0xc310_dcaa_d28e_16cd),
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for value in test_floats().chain(cases) {
+        buffer.clear();
+        buffer.write_f64_auto(value).unwrap();
+        assert_eq!(buffer.as_str(), format!("{value:?}"));
+    }
+
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_f64_auto(-1.0).is_err());
+    assert!(buffer.write_f64_auto(1.5e-7).is_err());
+    assert!(buffer.write_f64_auto(100.0).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_percent() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.write_percent(0.1234, 1).unwrap();
+    assert_eq!(buffer.as_str(), "12.3%");
+
+    let cases: &[(f64, usize, &str)] = &[
+        (1.0, 0, "100%"),
+        (0.5, 2, "50.00%"),
+        (-0.00125, 2, "-0.13%"),
+        (0.001, 0, "0%"),
+        (f64::NAN, 2, "NaN%"),
+        (f64::NEG_INFINITY, 2, "-inf%"),
+    ];
+
+    for &(fraction, precision, expected) in cases {
+        buffer.clear();
+        buffer.write_percent(fraction, precision).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_percent(0.1234, 2).is_err());
+    assert!(buffer.is_empty());
+}
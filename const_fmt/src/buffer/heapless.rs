@@ -0,0 +1,37 @@
+use super::{Buffer, BufferWriteFailed};
+use crate::ByteBuffer;
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Appends the written content to `out`, failing if it doesn't have enough room.
+    pub fn write_to_heapless<const M: usize>(
+        &self,
+        out: &mut ::heapless::String<M>,
+    ) -> Result<(), BufferWriteFailed> {
+        out.push_str(self.as_str()).map_err(|_| BufferWriteFailed)
+    }
+}
+
+impl<const N: usize> From<Buffer<[u8; N]>> for ::heapless::String<N> {
+    fn from(buffer: Buffer<[u8; N]>) -> Self {
+        let mut out = Self::new();
+        // both have a capacity of `N`, so this can't fail
+        let _ = out.push_str(buffer.as_str());
+        out
+    }
+}
+
+#[test]
+fn test_heapless() {
+    let mut buffer = Buffer::new::<8>();
+    buffer.write_u32(1234).unwrap();
+
+    let mut out = ::heapless::String::<6>::new();
+    out.push_str("n=").unwrap();
+    buffer.write_to_heapless(&mut out).unwrap();
+    assert_eq!(out, "n=1234");
+    assert!(buffer.write_to_heapless(&mut out).is_err());
+    assert_eq!(out, "n=1234");
+
+    let out = ::heapless::String::<8>::from(buffer);
+    assert_eq!(out, "1234");
+}
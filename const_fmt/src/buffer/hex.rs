@@ -0,0 +1,391 @@
+use super::{Buffer, BufferWriteFailed};
+use crate::ByteBuffer;
+
+pub(super) const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+pub(super) const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// The number of input bytes on each line of [`Buffer::write_hexdump`].
+pub const HEXDUMP_BYTES_PER_LINE: usize = 16;
+
+/// The order to write multi-byte values in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// First byte first.
+    BigEndian,
+    /// Last byte first.
+    LittleEndian,
+}
+
+// bytes per space-separated group in the hex column, like `xxd -g 2`
+const HEXDUMP_GROUP: usize = 2;
+const HEXDUMP_HEX_WIDTH: usize =
+    HEXDUMP_BYTES_PER_LINE * 2 + HEXDUMP_BYTES_PER_LINE.div_ceil(HEXDUMP_GROUP) - 1;
+
+impl<B: ByteBuffer> Buffer<B> {
+    // writes the lowest `digits` nibbles of `value`, most significant first
+    // there must be room for `digits` bytes
+    pub(super) const unsafe fn push_hex_unchecked(
+        &mut self,
+        value: u64,
+        digits: usize,
+        alphabet: &[u8; 16],
+    ) {
+        let mut i = digits;
+
+        while i > 0 {
+            i -= 1;
+            let nibble = if i < 16 { (value >> (4 * i)) & 0xf } else { 0 };
+            unsafe { self.push_byte_unchecked(alphabet[nibble as usize]) }
+        }
+    }
+
+    /// Writes an `xxd`-style dump of `bytes`.
+    ///
+    /// Each line holds [`HEXDUMP_BYTES_PER_LINE`] bytes: an offset of at least 8 hex
+    /// digits, the bytes in lowercase hex grouped in pairs, and an ASCII column with
+    /// non-printable bytes shown as `.`. Every line, including the last, ends in `\n`.
+    pub const fn write_hexdump(&mut self, bytes: &[u8]) -> Result<(), BufferWriteFailed> {
+        let mut len = 0usize;
+        let mut offset = 0;
+
+        while offset < bytes.len() {
+            let count = min(bytes.len() - offset, HEXDUMP_BYTES_PER_LINE);
+            let line_len = hexdump_offset_len(offset) + ": ".len() + HEXDUMP_HEX_WIDTH + "  ".len();
+            len = len.saturating_add(line_len + count + "\n".len());
+            offset += HEXDUMP_BYTES_PER_LINE;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut offset = 0;
+
+        while offset < bytes.len() {
+            let count = min(bytes.len() - offset, HEXDUMP_BYTES_PER_LINE);
+
+            unsafe {
+                self.push_hex_unchecked(offset as u64, hexdump_offset_len(offset), HEX_LOWER);
+                self.push_str_unchecked(": ");
+
+                let mut i = 0;
+                while i < HEXDUMP_BYTES_PER_LINE {
+                    if i != 0 && i % HEXDUMP_GROUP == 0 {
+                        self.push_byte_unchecked(b' ');
+                    }
+
+                    if i < count {
+                        self.push_hex_unchecked(bytes[offset + i] as u64, 2, HEX_LOWER);
+                    } else {
+                        self.push_str_unchecked("  ");
+                    }
+
+                    i += 1;
+                }
+
+                self.push_str_unchecked("  ");
+
+                let mut i = 0;
+                while i < count {
+                    let byte = bytes[offset + i];
+                    let printable = matches!(byte, b' '..=b'~');
+                    self.push_byte_unchecked(if printable { byte } else { b'.' });
+                    i += 1;
+                }
+
+                self.push_byte_unchecked(b'\n');
+            }
+
+            offset += HEXDUMP_BYTES_PER_LINE;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `bytes` as lowercase hex pairs with no separator, from the last byte to the
+    /// first if `order` is [`ByteOrder::LittleEndian`].
+    pub const fn write_bytes_hex_ordered(
+        &mut self,
+        bytes: &[u8],
+        order: ByteOrder,
+    ) -> Result<(), BufferWriteFailed> {
+        if bytes.len() > self.remaining_capacity() / 2 {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let byte = match order {
+                ByteOrder::BigEndian => bytes[i],
+                ByteOrder::LittleEndian => bytes[bytes.len() - 1 - i],
+            };
+
+            unsafe { self.push_hex_unchecked(byte as u64, 2, HEX_LOWER) };
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `bytes` as lowercase hex pairs with `sep` between them, like `de ad be ef`.
+    pub const fn write_bytes_hex_sep(
+        &mut self,
+        bytes: &[u8],
+        sep: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let len = match bytes.len() {
+            0 => 0,
+            n => n
+                .saturating_mul(2)
+                .saturating_add((n - 1).saturating_mul(sep.len_utf8())),
+        };
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if i != 0 {
+                // can't fail, there is room
+                let _ = self.write_char(sep);
+            }
+
+            unsafe { self.push_hex_unchecked(bytes[i] as u64, 2, HEX_LOWER) };
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `value` as a sign followed by its magnitude in lowercase hex, like `-0x2a`.
+    ///
+    /// The magnitude is zero padded to at least `min_digits` digits, and the `0x` prefix,
+    /// if `prefix` is set, goes between the sign and the padding: `-0x02a`.
+    pub const fn write_signed_magnitude_hex(
+        &mut self,
+        value: i64,
+        min_digits: usize,
+        prefix: bool,
+    ) -> Result<(), BufferWriteFailed> {
+        let magnitude = value.unsigned_abs();
+        let len = match magnitude.checked_ilog2() {
+            Some(log) => log as usize / 4 + 1,
+            None => 1,
+        };
+        let digits = if min_digits > len { min_digits } else { len };
+        let head = (value < 0) as usize + if prefix { 2 } else { 0 };
+
+        if digits.saturating_add(head) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            if value < 0 {
+                self.push_byte_unchecked(b'-');
+            }
+
+            if prefix {
+                self.push_str_unchecked("0x");
+            }
+
+            self.push_hex_unchecked(magnitude, digits, HEX_LOWER);
+        }
+
+        Ok(())
+    }
+
+    /// Writes a CSS-style `#rrggbb` color.
+    pub const fn write_hex_color(&mut self, rgb: [u8; 3]) -> Result<(), BufferWriteFailed> {
+        self.write_hex_color_imp(&rgb, HEX_LOWER)
+    }
+
+    /// Like [`write_hex_color`](Self::write_hex_color), but with uppercase hex digits.
+    pub const fn write_hex_color_upper(&mut self, rgb: [u8; 3]) -> Result<(), BufferWriteFailed> {
+        self.write_hex_color_imp(&rgb, HEX_UPPER)
+    }
+
+    /// Writes a CSS-style `#rrggbbaa` color.
+    pub const fn write_hex_color_alpha(&mut self, rgba: [u8; 4]) -> Result<(), BufferWriteFailed> {
+        self.write_hex_color_imp(&rgba, HEX_LOWER)
+    }
+
+    /// Like [`write_hex_color_alpha`](Self::write_hex_color_alpha), but with uppercase hex
+    /// digits.
+    pub const fn write_hex_color_alpha_upper(
+        &mut self,
+        rgba: [u8; 4],
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_hex_color_imp(&rgba, HEX_UPPER)
+    }
+
+    const fn write_hex_color_imp(
+        &mut self,
+        channels: &[u8],
+        alphabet: &[u8; 16],
+    ) -> Result<(), BufferWriteFailed> {
+        if 1 + channels.len() * 2 > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_byte_unchecked(b'#') };
+
+        let mut i = 0;
+
+        while i < channels.len() {
+            unsafe { self.push_hex_unchecked(channels[i] as u64, 2, alphabet) };
+            i += 1;
+        }
+
+        Ok(())
+    }
+}
+
+const fn min(a: usize, b: usize) -> usize {
+    if a < b { a } else { b }
+}
+
+const fn hexdump_offset_len(offset: usize) -> usize {
+    let digits = (usize::BITS - offset.leading_zeros()).div_ceil(4) as usize;
+    if digits < 8 { 8 } else { digits }
+}
+
+#[test]
+fn test_signed_magnitude_hex() {
+    let cases: &[(i64, usize, bool, &str)] = &[
+        (42, 0, true, "0x2a"),
+        (-42, 0, true, "-0x2a"),
+        (-42, 3, true, "-0x02a"),
+        (-42, 3, false, "-02a"),
+        (0, 0, true, "0x0"),
+        (0, 4, false, "0000"),
+        (255, 1, false, "ff"),
+        (i64::MIN, 0, true, "-0x8000000000000000"),
+        (i64::MAX, 20, true, "0x00007fffffffffffffff"),
+    ];
+
+    for &(value, min_digits, prefix, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer
+            .write_signed_magnitude_hex(value, min_digits, prefix)
+            .unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_signed_magnitude_hex(-42, 3, true).is_err());
+    assert!(
+        buffer
+            .write_signed_magnitude_hex(1, usize::MAX, true)
+            .is_err()
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_hexdump() {
+    let mut buffer = Buffer::new::<256>();
+    buffer.write_hexdump(b"Hello, world!\n").unwrap();
+    assert_eq!(
+        buffer.as_str(),
+        "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a       Hello, world!.\n"
+    );
+
+    let bytes: [u8; 40] = core::array::from_fn(|i| i as u8);
+    let mut buffer = Buffer::new::<256>();
+    buffer.write_hexdump(&bytes).unwrap();
+    assert_eq!(
+        buffer.as_str(),
+        "00000000: 0001 0203 0405 0607 0809 0a0b 0c0d 0e0f  ................\n\
+         00000010: 1011 1213 1415 1617 1819 1a1b 1c1d 1e1f  ................\n\
+         00000020: 2021 2223 2425 2627                       !\"#$%&'\n"
+    );
+}
+
+#[test]
+fn test_hexdump_capacity() {
+    let mut buffer = Buffer::new::<65>();
+    assert!(buffer.write_hexdump(b"Hello, world!\n").is_err());
+    assert!(buffer.is_empty());
+
+    let mut buffer = Buffer::new::<0>();
+    buffer.write_hexdump(b"").unwrap();
+}
+
+#[test]
+fn test_hex_color() {
+    let mut buffer = Buffer::new::<7>();
+    buffer.write_hex_color([0x1e, 0x90, 0xff]).unwrap();
+    assert_eq!(buffer.as_str(), "#1e90ff");
+
+    buffer.clear();
+    buffer.write_hex_color_upper([0x1e, 0x90, 0xff]).unwrap();
+    assert_eq!(buffer.as_str(), "#1E90FF");
+    assert!(buffer.write_hex_color([0; 3]).is_err());
+
+    let mut buffer = Buffer::new::<9>();
+    buffer.write_hex_color_alpha([0, 0x0a, 0xbc, 0x80]).unwrap();
+    assert_eq!(buffer.as_str(), "#000abc80");
+
+    buffer.clear();
+    buffer
+        .write_hex_color_alpha_upper([0, 0x0a, 0xbc, 0x80])
+        .unwrap();
+    assert_eq!(buffer.as_str(), "#000ABC80");
+}
+
+#[test]
+fn test_bytes_hex_ordered() {
+    let bytes = 0x1234_abcdu32.to_le_bytes();
+
+    let mut buffer = Buffer::new::<8>();
+    buffer
+        .write_bytes_hex_ordered(&bytes, ByteOrder::LittleEndian)
+        .unwrap();
+    assert_eq!(buffer.as_str(), "1234abcd");
+
+    buffer.clear();
+    buffer
+        .write_bytes_hex_ordered(&bytes, ByteOrder::BigEndian)
+        .unwrap();
+    assert_eq!(buffer.as_str(), "cdab3412");
+
+    buffer.clear();
+    buffer
+        .write_bytes_hex_ordered(&[], ByteOrder::LittleEndian)
+        .unwrap();
+    buffer
+        .write_bytes_hex_ordered(&[1, 2, 3], ByteOrder::BigEndian)
+        .unwrap();
+    assert!(
+        buffer
+            .write_bytes_hex_ordered(&[1, 2], ByteOrder::BigEndian)
+            .is_err()
+    );
+    assert_eq!(buffer.as_str(), "010203");
+}
+
+#[test]
+fn test_bytes_hex_sep() {
+    let cases: [(&[u8], char, &str); 5] = [
+        (&[0xde, 0xad, 0xbe, 0xef], ' ', "de ad be ef"),
+        (&[], ' ', ""),
+        (&[0x0a], ':', "0a"),
+        (&[0, 1, 0xff], ':', "00:01:ff"),
+        (&[0x12, 0x34], '·', "12·34"),
+    ];
+
+    let mut buffer = Buffer::new::<16>();
+
+    for (bytes, sep, expected) in cases {
+        buffer.clear();
+        buffer.write_bytes_hex_sep(bytes, sep).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<10>();
+    assert!(buffer.write_bytes_hex_sep(&[1, 2, 3, 4], ' ').is_err());
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,852 @@
+use super::{Buffer, BufferWriteFailed};
+use crate::ByteBuffer;
+
+macro_rules! write_binary_grouped {
+    ($ty:ident $writefun:ident) => {
+        /// Writes `value` in binary without leading zeros, inserting `sep` between every
+        /// `group` digits counted from the right. A `group` of `0` disables grouping.
+        pub const fn $writefun(
+            &mut self,
+            value: $ty,
+            group: usize,
+            sep: char,
+        ) -> Result<(), BufferWriteFailed> {
+            self.write_binary_grouped_imp(value as u128, group, sep)
+        }
+    };
+}
+
+macro_rules! digit_count {
+    ($ty:ident $fun:ident) => {
+        #[doc = concat!(
+            "The number of decimal digits [`Buffer::write_", stringify!($ty), "`] writes for ",
+            "`value`, so `0` has one.",
+        )]
+        pub const fn $fun(value: $ty) -> usize {
+            match value.checked_ilog10() {
+                Some(log) => log as usize + 1,
+                None => 1,
+            }
+        }
+    };
+}
+
+digit_count! { u8 u8_digit_count }
+digit_count! { u16 u16_digit_count }
+digit_count! { u32 u32_digit_count }
+digit_count! { u64 u64_digit_count }
+digit_count! { u128 u128_digit_count }
+digit_count! { usize usize_digit_count }
+
+/// The number of digits [`Buffer::write_u64_radix`] writes for `value`, so `0` has one.
+///
+/// # Panics
+///
+/// If `radix` is less than `2`.
+pub const fn u64_digit_count_radix(value: u64, radix: u32) -> usize {
+    assert!(radix >= 2, "radix must be at least 2");
+    radix_len(value, radix)
+}
+
+/// Why [`Buffer::write_u64_exact_width`] wrote nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WidthError {
+    /// The value fits the column, but not in the buffer.
+    BufferFull,
+    /// The value has more digits than the column is wide.
+    TooWide,
+}
+
+const RADIX_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes `value` in the given `radix`, using lowercase letters for digits past `9`.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is not in `2..=36`.
+    pub const fn write_u64_radix(
+        &mut self,
+        value: u64,
+        radix: u32,
+    ) -> Result<(), BufferWriteFailed> {
+        assert!(matches!(radix, 2..=36), "radix must be in 2..=36");
+
+        let len = radix_len(value, radix);
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_u64_radix_unchecked(value, radix, len) };
+
+        Ok(())
+    }
+
+    /// Writes `value` in base `radix`, using `alphabet[d]` for the digit `d`, e.g. with
+    /// Crockford's base 32 alphabet.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is less than `2`, if `alphabet` doesn't have exactly `radix` bytes, or if
+    /// any of them isn't ASCII.
+    pub const fn write_u64_radix_alphabet(
+        &mut self,
+        value: u64,
+        radix: u32,
+        alphabet: &[u8],
+    ) -> Result<(), BufferWriteFailed> {
+        assert!(radix >= 2, "radix must be at least 2");
+        assert!(
+            alphabet.len() == radix as usize,
+            "the alphabet must have exactly `radix` digits"
+        );
+        assert!(alphabet.is_ascii(), "the alphabet must be ASCII");
+
+        let len = radix_len(value, radix);
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_u64_alphabet_unchecked(value, alphabet, len) };
+
+        Ok(())
+    }
+
+    // `len` must be `radix_len(value, radix)` and there must be room for it
+    pub(super) const unsafe fn push_u64_radix_unchecked(
+        &mut self,
+        value: u64,
+        radix: u32,
+        len: usize,
+    ) {
+        unsafe {
+            self.push_u64_alphabet_unchecked(value, RADIX_DIGITS.split_at(radix as usize).0, len)
+        }
+    }
+
+    // `alphabet` must be ASCII, `len` must be `radix_len(value, alphabet.len())` and there
+    // must be room for it
+    const unsafe fn push_u64_alphabet_unchecked(
+        &mut self,
+        value: u64,
+        alphabet: &[u8],
+        len: usize,
+    ) {
+        let radix = alphabet.len() as u64;
+        let mut value = value;
+        let mut i = len;
+
+        unsafe {
+            let ptr = self.as_mut_ptr().add(self.len);
+
+            while i > 0 {
+                i -= 1;
+                ptr.add(i).write(alphabet[(value % radix) as usize]);
+                value /= radix;
+            }
+        }
+
+        self.len += len;
+    }
+
+    write_binary_grouped! { u8 write_u8_binary_grouped }
+    write_binary_grouped! { u16 write_u16_binary_grouped }
+    write_binary_grouped! { u32 write_u32_binary_grouped }
+    write_binary_grouped! { u64 write_u64_binary_grouped }
+    write_binary_grouped! { u128 write_u128_binary_grouped }
+
+    const fn write_binary_grouped_imp(
+        &mut self,
+        value: u128,
+        group: usize,
+        sep: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let digits = match value.checked_ilog2() {
+            Some(log) => log as usize + 1,
+            None => 1,
+        };
+        // a group of 0 means no separators
+        let seps = match (digits - 1).checked_div(group) {
+            Some(seps) => seps,
+            None => 0,
+        };
+
+        if digits + seps * sep.len_utf8() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = digits;
+
+        while i > 0 {
+            i -= 1;
+
+            if i + 1 != digits && group != 0 && (i + 1) % group == 0 {
+                tri!(self.write_char(sep));
+            }
+
+            let bit = (value >> i) & 1;
+            unsafe { self.push_byte_unchecked(b'0' + bit as u8) };
+        }
+
+        Ok(())
+    }
+
+    /// Writes each of `bits` as `0` or `1`, in order, so the first element comes out first.
+    pub const fn write_bits(&mut self, bits: &[bool]) -> Result<(), BufferWriteFailed> {
+        self.write_bits_grouped(bits, 0, '_')
+    }
+
+    /// Like [`write_bits`](Self::write_bits), but inserts `sep` between every `group` bits
+    /// counted from the right, like the `write_*_binary_grouped` methods. A `group` of `0`
+    /// disables grouping.
+    pub const fn write_bits_grouped(
+        &mut self,
+        bits: &[bool],
+        group: usize,
+        sep: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let digits = bits.len();
+        let seps = match digits.saturating_sub(1).checked_div(group) {
+            Some(seps) => seps,
+            None => 0,
+        };
+
+        if digits.saturating_add(seps.saturating_mul(sep.len_utf8())) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < digits {
+            let remaining = digits - i;
+
+            if i != 0 && group != 0 && remaining.is_multiple_of(group) {
+                tri!(self.write_char(sep));
+            }
+
+            unsafe { self.push_byte_unchecked(b'0' + bits[i] as u8) };
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `value` in decimal, zero padded to at least `min_digits` digits, with `sep`
+    /// between every `group` digits counted from the right, so the padding is grouped too:
+    /// `1234` with 7 digits in groups of 3 is `0_001_234`. A `group` of `0` disables
+    /// grouping.
+    pub const fn write_u64_formatted(
+        &mut self,
+        value: u64,
+        min_digits: usize,
+        group: usize,
+        sep: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let len = u64_digit_count(value);
+        let digits = if min_digits > len { min_digits } else { len };
+        let seps = match (digits - 1).checked_div(group) {
+            Some(seps) => seps,
+            None => 0,
+        };
+
+        if digits.saturating_add(seps.saturating_mul(sep.len_utf8())) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = digits;
+
+        while i > 0 {
+            i -= 1;
+
+            if i + 1 != digits && group != 0 && (i + 1) % group == 0 {
+                tri!(self.write_char(sep));
+            }
+
+            let digit = if i < len {
+                value / 10u64.pow(i as u32) % 10
+            } else {
+                0
+            };
+            unsafe { self.push_byte_unchecked(b'0' + digit as u8) };
+        }
+
+        Ok(())
+    }
+
+    /// Writes `value` as a Rust integer literal would commonly be written, with `_` between
+    /// every three digits counted from the right, like `1_000_000`. Values below `1000`
+    /// have no underscores.
+    pub const fn write_u64_rust_literal(&mut self, value: u64) -> Result<(), BufferWriteFailed> {
+        self.write_u64_formatted(value, 1, 3, '_')
+    }
+
+    /// Writes `value` in decimal, zero padded to at least `min_digits` digits. Wider values
+    /// are written as they are.
+    pub const fn write_u128_padded(
+        &mut self,
+        value: u128,
+        min_digits: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        let len = u128_digit_count(value);
+        let zeros = min_digits.saturating_sub(len);
+
+        if zeros.saturating_add(len) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < zeros {
+            unsafe { self.push_byte_unchecked(b'0') };
+            i += 1;
+        }
+
+        self.write_u128(value)
+    }
+
+    /// Writes `value` in decimal zero padded to exactly `width` digits, for columns that
+    /// must never grow: unlike [`write_u128_padded`](Self::write_u128_padded), a value with
+    /// more than `width` digits is an error instead of being written whole.
+    ///
+    /// Nothing is written on failure. A too wide value is reported as
+    /// [`WidthError::TooWide`] even when it also wouldn't fit in the buffer.
+    pub const fn write_u64_exact_width(
+        &mut self,
+        value: u64,
+        width: usize,
+    ) -> Result<(), WidthError> {
+        let len = u64_digit_count(value);
+
+        if len > width {
+            return Err(WidthError::TooWide);
+        }
+
+        if width > self.remaining_capacity() {
+            return Err(WidthError::BufferFull);
+        }
+
+        let mut i = len;
+
+        while i < width {
+            unsafe { self.push_byte_unchecked(b'0') };
+            i += 1;
+        }
+
+        // can't fail, there is room
+        let _ = self.write_u64(value);
+
+        Ok(())
+    }
+
+    /// Writes `value` in decimal with its magnitude zero padded to at least `min_digits`
+    /// digits, and the `-` of a negative value before the padding, so `-7` with 4 digits
+    /// is `-0007`. The sign doesn't count toward `min_digits`.
+    pub const fn write_i64_zero_padded(
+        &mut self,
+        value: i64,
+        min_digits: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        let negative = value < 0;
+        let len = u64_digit_count(value.unsigned_abs());
+        let zeros = min_digits.saturating_sub(len);
+
+        if (negative as usize + len).saturating_add(zeros) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            if negative {
+                self.push_byte_unchecked(b'-');
+            }
+
+            let mut i = 0;
+
+            while i < zeros {
+                self.push_byte_unchecked(b'0');
+                i += 1;
+            }
+        }
+
+        self.write_u64(value.unsigned_abs())
+    }
+
+    /// Writes the range from `start` to `end` like Rust's range syntax, `3..7`, or `3..=7`
+    /// if `inclusive` is set.
+    ///
+    /// Nothing is written if the whole range doesn't fit.
+    pub const fn write_range(
+        &mut self,
+        start: i64,
+        end: i64,
+        inclusive: bool,
+    ) -> Result<(), BufferWriteFailed> {
+        let op = if inclusive { "..=" } else { ".." };
+        let len = (start < 0) as usize
+            + u64_digit_count(start.unsigned_abs())
+            + op.len()
+            + (end < 0) as usize
+            + u64_digit_count(end.unsigned_abs());
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        tri!(self.write_i64(start));
+        tri!(self.push_str(op));
+        self.write_i64(end)
+    }
+
+    /// Writes `prefix`, then `value` in decimal, then `suffix`, so `"v"`, `42` and `";"`
+    /// become `v42;`.
+    ///
+    /// The capacity is checked once for all three pieces, so unlike three separate writes
+    /// this never leaves a partial `prefix` behind: either everything is written, or
+    /// nothing is.
+    pub const fn write_u64_wrapped(
+        &mut self,
+        prefix: &str,
+        value: u64,
+        suffix: &str,
+    ) -> Result<(), BufferWriteFailed> {
+        let len = prefix
+            .len()
+            .saturating_add(u64_digit_count(value))
+            .saturating_add(suffix.len());
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_str_unchecked(prefix) };
+        tri!(self.write_u64(value));
+        unsafe { self.push_str_unchecked(suffix) };
+
+        Ok(())
+    }
+
+    /// Writes each of `values` in decimal, with `sep` between them.
+    ///
+    /// Nothing is written if the whole list doesn't fit.
+    pub const fn write_slice_u32(
+        &mut self,
+        values: &[u32],
+        sep: &str,
+    ) -> Result<(), BufferWriteFailed> {
+        let mut len = 0;
+        let mut i = 0;
+
+        while i < values.len() {
+            if i != 0 {
+                len += sep.len();
+            }
+
+            len += u64_digit_count(values[i] as u64);
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < values.len() {
+            if i != 0 {
+                tri!(self.push_str(sep));
+            }
+
+            tri!(self.write_u32(values[i]));
+            i += 1;
+        }
+
+        Ok(())
+    }
+}
+
+pub(super) const fn radix_len(value: u64, radix: u32) -> usize {
+    let mut value = value;
+    let mut len = 1;
+
+    while value >= radix as u64 {
+        value /= radix as u64;
+        len += 1;
+    }
+
+    len
+}
+
+#[test]
+fn test_u64_radix() {
+    for value in [0, 1, 35, 36, 255, 1 << 40, u64::MAX] {
+        let mut buffer = Buffer::new::<64>();
+
+        buffer.write_u64_radix(value, 2).unwrap();
+        assert_eq!(buffer.as_str(), format!("{value:b}"));
+
+        buffer.clear();
+        buffer.write_u64_radix(value, 8).unwrap();
+        assert_eq!(buffer.as_str(), format!("{value:o}"));
+
+        buffer.clear();
+        buffer.write_u64_radix(value, 16).unwrap();
+        assert_eq!(buffer.as_str(), format!("{value:x}"));
+    }
+
+    let mut buffer = Buffer::new::<4>();
+    buffer.write_u64_radix(46655, 36).unwrap();
+    assert_eq!(buffer.as_str(), "zzz");
+}
+
+#[test]
+fn test_u64_radix_alphabet() {
+    const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+    let mut buffer = Buffer::new::<16>();
+    buffer.write_u64_radix_alphabet(0, 32, CROCKFORD).unwrap();
+    assert_eq!(buffer.as_str(), "0");
+
+    buffer.clear();
+    buffer
+        .write_u64_radix_alphabet(1_234_567, 32, CROCKFORD)
+        .unwrap();
+    assert_eq!(buffer.as_str(), "15NM7");
+
+    buffer.clear();
+    buffer.write_u64_radix_alphabet(5, 2, b"-+").unwrap();
+    assert_eq!(buffer.as_str(), "+-+");
+
+    buffer.clear();
+    buffer
+        .write_u64_radix_alphabet(u64::MAX, 16, b"0123456789ABCDEF")
+        .unwrap();
+    assert_eq!(buffer.as_str(), "FFFFFFFFFFFFFFFF");
+
+    assert!(buffer.write_u64_radix_alphabet(1, 32, CROCKFORD).is_err());
+}
+
+#[test]
+#[should_panic = "the alphabet must have exactly `radix` digits"]
+fn test_u64_radix_alphabet_mismatch() {
+    let _ = Buffer::new::<16>().write_u64_radix_alphabet(7, 10, b"0123456789ab");
+}
+
+#[test]
+#[should_panic = "the alphabet must be ASCII"]
+fn test_u64_radix_alphabet_non_ascii() {
+    let _ = Buffer::new::<16>().write_u64_radix_alphabet(7, 2, &[b'0', 0xff]);
+}
+
+#[test]
+fn test_slice_u32() {
+    let mut buffer = Buffer::new::<32>();
+    buffer.write_slice_u32(&[1, 2, 30], ", ").unwrap();
+    assert_eq!(buffer.as_str(), "1, 2, 30");
+
+    buffer.clear();
+    buffer.write_slice_u32(&[], ", ").unwrap();
+    buffer.write_slice_u32(&[0, u32::MAX], "").unwrap();
+    assert_eq!(buffer.as_str(), "04294967295");
+
+    let mut buffer = Buffer::new::<7>();
+    assert!(buffer.write_slice_u32(&[1, 2, 30], ", ").is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_u64_formatted() {
+    let cases: &[(u64, usize, usize, char, &str)] = &[
+        (1234, 7, 3, '_', "0_001_234"),
+        (1234, 0, 3, ',', "1,234"),
+        (1234, 6, 3, ',', "001,234"),
+        (0, 0, 3, ',', "0"),
+        (0, 4, 2, ' ', "00 00"),
+        (42, 5, 0, '_', "00042"),
+        (u64::MAX, 0, 3, ',', "18,446,744,073,709,551,615"),
+        (7, 22, 4, '.', "00.0000.0000.0000.0000.0007"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for &(value, min_digits, group, sep, expected) in cases {
+        buffer.clear();
+        buffer
+            .write_u64_formatted(value, min_digits, group, sep)
+            .unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<8>();
+    assert!(buffer.write_u64_formatted(1234, 7, 3, '_').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_u128_padded() {
+    let cases = [
+        (0, 0),
+        (0, 1),
+        (7, 39),
+        (42, 3),
+        (12345, 2),
+        (u128::MAX, 0),
+        (u128::MAX, 39),
+        (u128::MAX, 45),
+        (u64::MAX as u128 + 1, 30),
+    ];
+
+    let mut buffer = Buffer::new::<64>();
+
+    for (value, min_digits) in cases {
+        buffer.clear();
+        buffer.write_u128_padded(value, min_digits).unwrap();
+        assert_eq!(buffer.as_str(), format!("{value:0min_digits$}"));
+    }
+
+    let mut buffer = Buffer::new::<38>();
+    assert!(buffer.write_u128_padded(1, 39).is_err());
+    assert!(buffer.write_u128_padded(u128::MAX, 0).is_err());
+    assert!(buffer.write_u128_padded(1, usize::MAX).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_u64_exact_width() {
+    let cases = [
+        (7, 4, "0007"),
+        (0, 1, "0"),
+        (1234, 4, "1234"),
+        (u64::MAX, 20, "18446744073709551615"),
+        (u64::MAX, 25, "0000018446744073709551615"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for (value, width, expected) in cases {
+        buffer.clear();
+        buffer.write_u64_exact_width(value, width).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    buffer.clear();
+    assert_eq!(
+        buffer.write_u64_exact_width(12345, 4),
+        Err(WidthError::TooWide)
+    );
+    assert_eq!(buffer.write_u64_exact_width(0, 0), Err(WidthError::TooWide));
+    assert!(buffer.is_empty());
+
+    let mut buffer = Buffer::new::<3>();
+    assert_eq!(
+        buffer.write_u64_exact_width(7, 4),
+        Err(WidthError::BufferFull)
+    );
+    assert_eq!(
+        buffer.write_u64_exact_width(12345, 4),
+        Err(WidthError::TooWide)
+    );
+    assert_eq!(
+        buffer.write_u64_exact_width(1, usize::MAX),
+        Err(WidthError::BufferFull)
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_i64_zero_padded() {
+    let cases = [
+        (-7, 4, "-0007"),
+        (7, 4, "0007"),
+        (0, 3, "000"),
+        (0, 0, "0"),
+        (-12345, 3, "-12345"),
+        (i64::MIN, 0, "-9223372036854775808"),
+        (i64::MIN, 21, "-009223372036854775808"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for (value, min_digits, expected) in cases {
+        buffer.clear();
+        buffer.write_i64_zero_padded(value, min_digits).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+
+        // `{:0N}` counts the sign toward the width
+        let width = min_digits + (value < 0) as usize;
+        assert_eq!(buffer.as_str(), format!("{value:0width$}"));
+    }
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_i64_zero_padded(-7, 4).is_err());
+    assert!(buffer.write_i64_zero_padded(1, usize::MAX).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_range() {
+    let cases = [
+        (3, 7, false),
+        (3, 7, true),
+        (-5, -1, false),
+        (0, 0, true),
+        (i64::MIN, i64::MAX, true),
+    ];
+
+    let mut buffer = Buffer::new::<48>();
+
+    for (start, end, inclusive) in cases {
+        buffer.clear();
+        buffer.write_range(start, end, inclusive).unwrap();
+
+        let expected = if inclusive {
+            format!("{:?}", start..=end)
+        } else {
+            format!("{:?}", start..end)
+        };
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<6>();
+    assert!(buffer.write_range(-10, 10, true).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_u64_wrapped() {
+    let cases = [
+        ("v", 42, ";", "v42;"),
+        ("", 0, "", "0"),
+        ("[", u64::MAX, "]", "[18446744073709551615]"),
+        ("é=", 7, " ✓", "é=7 ✓"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for (prefix, value, suffix, expected) in cases {
+        buffer.clear();
+        buffer.write_u64_wrapped(prefix, value, suffix).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    // each piece fits on its own, but not all three together
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_u64_wrapped("v", 42, ";;").is_err());
+    assert!(buffer.is_empty());
+    buffer.write_u64_wrapped("v", 42, ";").unwrap();
+    assert_eq!(buffer.as_str(), "v42;");
+}
+
+#[test]
+fn test_u64_rust_literal() {
+    let cases = [
+        (0, "0"),
+        (999, "999"),
+        (1000, "1_000"),
+        (1_000_000, "1_000_000"),
+        (12_345_678, "12_345_678"),
+        (u64::MAX, "18_446_744_073_709_551_615"),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for (value, expected) in cases {
+        buffer.clear();
+        buffer.write_u64_rust_literal(value).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    for value in crate::test_rng::random_u64s(0x1_000).take(1000) {
+        buffer.clear();
+        buffer.write_u64_rust_literal(value).unwrap();
+        assert_eq!(buffer.as_str().replace('_', ""), value.to_string());
+        // only the leftmost group can be shorter
+        let mut groups = buffer.as_str().split('_').skip(1);
+        assert!(groups.all(|group| group.len() == 3));
+    }
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_u64_rust_literal(1000).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_binary_grouped() {
+    let mut buffer = Buffer::new::<64>();
+    buffer.write_u16_binary_grouped(0xa5c3, 4, '_').unwrap();
+    assert_eq!(buffer.as_str(), "1010_0101_1100_0011");
+
+    buffer.clear();
+    buffer.write_u8_binary_grouped(0, 4, '_').unwrap();
+    assert_eq!(buffer.as_str(), "0");
+
+    buffer.clear();
+    buffer.write_u32_binary_grouped(0b10_1100, 4, ' ').unwrap();
+    assert_eq!(buffer.as_str(), "10 1100");
+
+    buffer.clear();
+    buffer.write_u64_binary_grouped(0b1011, 0, '_').unwrap();
+    assert_eq!(buffer.as_str(), "1011");
+
+    let mut buffer = Buffer::new::<18>();
+    assert!(buffer.write_u16_binary_grouped(0xa5c3, 4, '_').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_bits() {
+    let mut buffer = Buffer::new::<32>();
+    buffer.write_bits(&[true, false, true, true]).unwrap();
+    assert_eq!(buffer.as_str(), "1011");
+
+    buffer.clear();
+    buffer.write_bits(&[]).unwrap();
+    assert_eq!(buffer.as_str(), "");
+
+    let bits = [
+        true, false, false, true, true, false, true, false, true, true,
+    ];
+
+    buffer.clear();
+    buffer.write_bits_grouped(&bits, 4, '_').unwrap();
+    assert_eq!(buffer.as_str(), "10_0110_1011");
+
+    buffer.clear();
+    buffer.write_bits_grouped(&bits[..8], 4, ' ').unwrap();
+    assert_eq!(buffer.as_str(), "1001 1010");
+
+    buffer.clear();
+    buffer.write_bits_grouped(&bits, 0, '_').unwrap();
+    assert_eq!(buffer.as_str(), "1001101011");
+
+    const BITS: Buffer<[u8; 8]> = {
+        let mut buffer = Buffer::new::<8>();
+        let _ = buffer.write_bits(&[false; 8]);
+        buffer
+    };
+    assert_eq!(BITS.as_str(), "00000000");
+
+    let mut buffer = Buffer::new::<11>();
+    assert!(buffer.write_bits_grouped(&bits, 4, '_').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_digit_count() {
+    for value in [0, 1, 9, 10, 99, 100, 12_345, u64::MAX] {
+        assert_eq!(u64_digit_count(value), value.to_string().len());
+        assert_eq!(u128_digit_count(value as u128), value.to_string().len());
+        assert_eq!(u64_digit_count_radix(value, 2), format!("{value:b}").len());
+        assert_eq!(u64_digit_count_radix(value, 16), format!("{value:x}").len());
+    }
+
+    assert_eq!(u8_digit_count(255), 3);
+    assert_eq!(u16_digit_count(1000), 4);
+    assert_eq!(u32_digit_count(u32::MAX), 10);
+    assert_eq!(u128_digit_count(u128::MAX), 39);
+    assert_eq!(usize_digit_count(0), 1);
+    assert_eq!(u64_digit_count_radix(u64::MAX, 1000), 7);
+
+    const WIDTH: usize = u32_digit_count(65_536);
+    assert_eq!(WIDTH, 5);
+}
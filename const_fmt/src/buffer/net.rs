@@ -0,0 +1,264 @@
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+use super::hex::{HEX_LOWER, HEX_UPPER};
+use super::{Buffer, BufferWriteFailed};
+use crate::ByteBuffer;
+
+// the longest outputs, like `255.255.255.255` and `[ffff:...:ffff%4294967295]:65535`
+const IPV4_MAX_LEN: usize = 15;
+const IPV6_MAX_LEN: usize = 39;
+const SOCKET_V6_MAX_LEN: usize = IPV6_MAX_LEN + "[%4294967295]:65535".len();
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes a MAC address as lowercase hex pairs joined by `sep`, e.g. `01:23:45:67:89:ab`.
+    pub const fn write_mac(&mut self, bytes: [u8; 6], sep: char) -> Result<(), BufferWriteFailed> {
+        self.write_mac_imp(bytes, sep, HEX_LOWER)
+    }
+
+    /// Like [`write_mac`](Self::write_mac), but with uppercase hex digits.
+    pub const fn write_mac_upper(
+        &mut self,
+        bytes: [u8; 6],
+        sep: char,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_mac_imp(bytes, sep, HEX_UPPER)
+    }
+
+    /// Writes an IPv4 address in dotted decimal, like `127.0.0.1`.
+    pub const fn write_ipv4(&mut self, addr: Ipv4Addr) -> Result<(), BufferWriteFailed> {
+        let mut scratch = Buffer::new::<IPV4_MAX_LEN>();
+        scratch.push_ipv4(addr);
+        self.push_str(scratch.as_str())
+    }
+
+    /// Writes an IPv6 address like its `Display` impl does: lowercase hex, with the longest
+    /// run of zero groups shortened to `::`, and IPv4-mapped addresses as `::ffff:1.2.3.4`.
+    pub const fn write_ipv6(&mut self, addr: Ipv6Addr) -> Result<(), BufferWriteFailed> {
+        let mut scratch = Buffer::new::<IPV6_MAX_LEN>();
+        scratch.push_ipv6(addr);
+        self.push_str(scratch.as_str())
+    }
+
+    /// Writes a socket address as `ip:port`, like `127.0.0.1:8080`.
+    pub const fn write_socket_addr_v4(
+        &mut self,
+        addr: SocketAddrV4,
+    ) -> Result<(), BufferWriteFailed> {
+        let mut scratch = Buffer::new::<{ IPV4_MAX_LEN + ":65535".len() }>();
+        scratch.push_ipv4(*addr.ip());
+        let _ = scratch.push_str(":");
+        let _ = scratch.write_u16(addr.port());
+        self.push_str(scratch.as_str())
+    }
+
+    /// Writes a socket address as `[ip]:port`, like `[::1]:8080`, with a non-zero scope id
+    /// as `[ip%scope]:port`.
+    pub const fn write_socket_addr_v6(
+        &mut self,
+        addr: SocketAddrV6,
+    ) -> Result<(), BufferWriteFailed> {
+        let mut scratch = Buffer::new::<SOCKET_V6_MAX_LEN>();
+        let _ = scratch.push_str("[");
+        scratch.push_ipv6(*addr.ip());
+
+        if addr.scope_id() != 0 {
+            let _ = scratch.push_str("%");
+            let _ = scratch.write_u32(addr.scope_id());
+        }
+
+        let _ = scratch.push_str("]:");
+        let _ = scratch.write_u16(addr.port());
+        self.push_str(scratch.as_str())
+    }
+
+    // there must be room for `IPV4_MAX_LEN` bytes
+    const fn push_ipv4(&mut self, addr: Ipv4Addr) {
+        let octets = addr.octets();
+        let mut i = 0;
+
+        while i < octets.len() {
+            if i != 0 {
+                let _ = self.push_str(".");
+            }
+
+            let _ = self.write_u8(octets[i]);
+            i += 1;
+        }
+    }
+
+    // there must be room for `IPV6_MAX_LEN` bytes
+    const fn push_ipv6(&mut self, addr: Ipv6Addr) {
+        let segments = addr.segments();
+
+        if let [0, 0, 0, 0, 0, 0xffff, hi, lo] = segments {
+            let _ = self.push_str("::ffff:");
+            let [a, b] = hi.to_be_bytes();
+            let [c, d] = lo.to_be_bytes();
+            return self.push_ipv4(Ipv4Addr::new(a, b, c, d));
+        }
+
+        // find the first longest run of at least two zero groups
+        let mut zeros_start = 0;
+        let mut zeros_len = 0;
+        let mut i = 0;
+
+        while i < segments.len() {
+            let mut end = i;
+
+            while end < segments.len() && segments[end] == 0 {
+                end += 1;
+            }
+
+            if end - i > zeros_len && end - i > 1 {
+                zeros_start = i;
+                zeros_len = end - i;
+            }
+
+            i = if end > i { end } else { i + 1 };
+        }
+
+        let mut i = 0;
+
+        while i < segments.len() {
+            if zeros_len != 0 && i == zeros_start {
+                let _ = self.push_str("::");
+                i += zeros_len;
+                continue;
+            }
+
+            if i != 0 && !(zeros_len != 0 && i == zeros_start + zeros_len) {
+                let _ = self.push_str(":");
+            }
+
+            let _ = self.write_u64_radix(segments[i] as u64, 16);
+            i += 1;
+        }
+    }
+
+    const fn write_mac_imp(
+        &mut self,
+        bytes: [u8; 6],
+        sep: char,
+        alphabet: &[u8; 16],
+    ) -> Result<(), BufferWriteFailed> {
+        if 12 + 5 * sep.len_utf8() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if i != 0 {
+                tri!(self.write_char(sep));
+            }
+
+            unsafe { self.push_hex_unchecked(bytes[i] as u64, 2, alphabet) };
+            i += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_mac() {
+    let mac = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab];
+
+    let mut buffer = Buffer::new::<17>();
+    buffer.write_mac(mac, ':').unwrap();
+    assert_eq!(buffer.as_str(), "01:23:45:67:89:ab");
+    assert!(buffer.write_mac(mac, ':').is_err());
+
+    let mut buffer = Buffer::new::<17>();
+    buffer.write_mac_upper(mac, '-').unwrap();
+    assert_eq!(buffer.as_str(), "01-23-45-67-89-AB");
+}
+
+#[test]
+fn test_ip() {
+    let v4 = ["0.0.0.0", "127.0.0.1", "255.255.255.255", "10.0.200.3"];
+
+    for addr in v4 {
+        let addr: Ipv4Addr = addr.parse().unwrap();
+        let mut buffer = Buffer::new::<15>();
+        buffer.write_ipv4(addr).unwrap();
+        assert_eq!(buffer.as_str(), addr.to_string());
+    }
+
+    let v6 = [
+        "::",
+        "::1",
+        "1::",
+        "2001:db8::8a2e:370:7334",
+        "2001:db8:0:0:1:0:0:1",
+        "2001:0:0:1:0:0:0:1",
+        "1:0:1:0:1:0:1:0",
+        "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff",
+        "::ffff:192.168.0.1",
+        "::192.168.0.1",
+        "fe80::1:2",
+    ];
+
+    for addr in v6 {
+        let addr: Ipv6Addr = addr.parse().unwrap();
+        let mut buffer = Buffer::new::<39>();
+        buffer.write_ipv6(addr).unwrap();
+        assert_eq!(buffer.as_str(), addr.to_string());
+    }
+
+    let mut random = crate::test_rng::random_u64s(0x6a09_e667_f3bc_c908);
+
+    for _ in 0..1000 {
+        // sprinkle in zero groups so that `::` shows up often
+        let mask = random.next().unwrap();
+        let bits = random.next().unwrap() as u128 | (random.next().unwrap() as u128) << 64;
+        let segments: [u16; 8] = core::array::from_fn(|i| {
+            if mask >> i & 1 == 0 {
+                0
+            } else {
+                (bits >> (16 * i)) as u16
+            }
+        });
+
+        let addr = Ipv6Addr::from(segments);
+        let mut buffer = Buffer::new::<39>();
+        buffer.write_ipv6(addr).unwrap();
+        assert_eq!(buffer.as_str(), addr.to_string());
+    }
+
+    let mut buffer = Buffer::new::<14>();
+    assert!(
+        buffer
+            .write_ipv4("255.255.255.255".parse().unwrap())
+            .is_err()
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_socket_addr() {
+    let mut buffer = Buffer::new::<64>();
+
+    for addr in ["127.0.0.1:8080", "0.0.0.0:0", "255.255.255.255:65535"] {
+        let addr: SocketAddrV4 = addr.parse().unwrap();
+        buffer.clear();
+        buffer.write_socket_addr_v4(addr).unwrap();
+        assert_eq!(buffer.as_str(), addr.to_string());
+    }
+
+    let addrs = [
+        SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0),
+        SocketAddrV6::new("fe80::1".parse().unwrap(), 443, 7, 5),
+        SocketAddrV6::new(Ipv6Addr::from([0xffff; 8]), 65535, 0, u32::MAX),
+    ];
+
+    for addr in addrs {
+        buffer.clear();
+        buffer.write_socket_addr_v6(addr).unwrap();
+        assert_eq!(buffer.as_str(), addr.to_string());
+    }
+
+    let mut buffer = Buffer::new::<9>();
+    assert!(buffer.write_socket_addr_v6(addrs[0]).is_err());
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,353 @@
+use super::{Buffer, BufferWriteFailed, u64_digit_count};
+use crate::ByteBuffer;
+
+const ROMAN: [(u16, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+const NATO_LETTERS: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "Xray", "Yankee", "Zulu",
+];
+const NATO_DIGITS: [&str; 10] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine",
+];
+
+const MORSE_LETTERS: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+];
+const MORSE_DIGITS: [&str; 10] = [
+    "-----", ".----", "..---", "...--", "....-", ".....", "-....", "--...", "---..", "----.",
+];
+
+// the code of an ASCII letter or digit, in either case, or `/` for a space
+const fn morse_code(byte: u8) -> Option<&'static str> {
+    match byte {
+        b'a'..=b'z' => Some(MORSE_LETTERS[(byte - b'a') as usize]),
+        b'A'..=b'Z' => Some(MORSE_LETTERS[(byte - b'A') as usize]),
+        b'0'..=b'9' => Some(MORSE_DIGITS[(byte - b'0') as usize]),
+        b' ' => Some("/"),
+        _ => None,
+    }
+}
+
+// the spelling of an ASCII letter or digit, in either case
+const fn nato_word(byte: u8) -> Option<&'static str> {
+    match byte {
+        b'a'..=b'z' => Some(NATO_LETTERS[(byte - b'a') as usize]),
+        b'A'..=b'Z' => Some(NATO_LETTERS[(byte - b'A') as usize]),
+        b'0'..=b'9' => Some(NATO_DIGITS[(byte - b'0') as usize]),
+        _ => None,
+    }
+}
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes `value` in upper case Roman numerals, e.g. `MCMXCIV`.
+    ///
+    /// Fails without writing anything if `value` isn't in `1..=3999`.
+    pub const fn write_roman(&mut self, value: u16) -> Result<(), BufferWriteFailed> {
+        if !matches!(value, 1..=3999) {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut len = 0;
+        let mut rest = value;
+        let mut i = 0;
+
+        while i < ROMAN.len() {
+            let (step, numeral) = ROMAN[i];
+            len += (rest / step) as usize * numeral.len();
+            rest %= step;
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut rest = value;
+        let mut i = 0;
+
+        while i < ROMAN.len() {
+            let (step, numeral) = ROMAN[i];
+
+            while rest >= step {
+                unsafe { self.push_str_unchecked(numeral) };
+                rest -= step;
+            }
+
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Spells out the ASCII letters and digits of `s` in the NATO phonetic alphabet,
+    /// separated by spaces, so `AB1` is `Alfa Bravo One`.
+    ///
+    /// Letters are spelled the same in either case, and digits as their English names.
+    /// Every other character, including spaces and non-ASCII ones, is skipped, so the
+    /// output is only words.
+    pub const fn write_nato(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        let bytes = s.as_bytes();
+        let mut len = 0usize;
+        let mut words = 0usize;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if let Some(word) = nato_word(bytes[i]) {
+                len = len.saturating_add(word.len());
+                words += 1;
+            }
+
+            i += 1;
+        }
+
+        if len.saturating_add(words.saturating_sub(1)) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut first = true;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if let Some(word) = nato_word(bytes[i]) {
+                unsafe {
+                    if !first {
+                        self.push_byte_unchecked(b' ');
+                    }
+
+                    self.push_str_unchecked(word);
+                }
+
+                first = false;
+            }
+
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the ASCII letters and digits of `s` in Morse code, separated by spaces, so
+    /// `SOS 1` is `... --- ... / .----`.
+    ///
+    /// Letters are encoded the same in either case, and each space becomes a `/` between
+    /// words. Every other character, including non-ASCII ones, is skipped.
+    pub const fn write_morse(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        let bytes = s.as_bytes();
+        let mut len = 0usize;
+        let mut codes = 0usize;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if let Some(code) = morse_code(bytes[i]) {
+                len = len.saturating_add(code.len());
+                codes += 1;
+            }
+
+            i += 1;
+        }
+
+        if len.saturating_add(codes.saturating_sub(1)) > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut first = true;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if let Some(code) = morse_code(bytes[i]) {
+                unsafe {
+                    if !first {
+                        self.push_byte_unchecked(b' ');
+                    }
+
+                    self.push_str_unchecked(code);
+                }
+
+                first = false;
+            }
+
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `n` with its English ordinal suffix: `1st`, `2nd`, `3rd`, `4th`, `11th`, `21st`.
+    pub const fn write_ordinal(&mut self, n: u64) -> Result<(), BufferWriteFailed> {
+        let suffix = match (n % 10, n % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+
+        if u64_digit_count(n) + suffix.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        tri!(self.write_u64(n));
+        self.push_str(suffix)
+    }
+}
+
+#[cfg(test)]
+fn roman_reference(value: u16) -> String {
+    let digit = |d: u16, one: &str, five: &str, ten: &str| match d {
+        0..=3 => one.repeat(d as usize),
+        4 => format!("{one}{five}"),
+        5..=8 => format!("{five}{}", one.repeat(d as usize - 5)),
+        _ => format!("{one}{ten}"),
+    };
+
+    let thousands = "M".repeat(value as usize / 1000);
+    let hundreds = digit(value / 100 % 10, "C", "D", "M");
+    let tens = digit(value / 10 % 10, "X", "L", "C");
+    let ones = digit(value % 10, "I", "V", "X");
+    thousands + &hundreds + &tens + &ones
+}
+
+#[test]
+fn test_roman() {
+    for value in 1..=3999 {
+        let mut buffer = Buffer::new::<16>();
+        buffer.write_roman(value).unwrap();
+        assert_eq!(buffer.as_str(), roman_reference(value), "{value}");
+    }
+
+    let mut buffer = Buffer::new::<16>();
+    buffer.write_roman(1994).unwrap();
+    assert_eq!(buffer.as_str(), "MCMXCIV");
+
+    assert!(buffer.write_roman(0).is_err());
+    assert!(buffer.write_roman(4000).is_err());
+
+    // MMMDCCCLXXXVIII is the longest numeral
+    let mut buffer = Buffer::new::<14>();
+    assert!(buffer.write_roman(3888).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_ordinal() {
+    let cases = [
+        (0, "0th"),
+        (1, "1st"),
+        (2, "2nd"),
+        (3, "3rd"),
+        (4, "4th"),
+        (11, "11th"),
+        (12, "12th"),
+        (13, "13th"),
+        (21, "21st"),
+        (22, "22nd"),
+        (23, "23rd"),
+        (101, "101st"),
+        (111, "111th"),
+        (112, "112th"),
+        (1013, "1013th"),
+        (u64::MAX, "18446744073709551615th"),
+    ];
+
+    for (n, expected) in cases {
+        let mut buffer = Buffer::new::<22>();
+        buffer.write_ordinal(n).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_ordinal(11).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_nato() {
+    let cases = [
+        ("", ""),
+        ("AB1", "Alfa Bravo One"),
+        ("sos", "Sierra Oscar Sierra"),
+        ("x-ray 9!", "Xray Romeo Alfa Yankee Nine"),
+        ("é✓ -", ""),
+        ("Q0", "Quebec Zero"),
+    ];
+
+    let mut buffer = Buffer::new::<64>();
+
+    for (s, expected) in cases {
+        buffer.clear();
+        buffer.write_nato(s).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{s:?}");
+    }
+
+    let alphabet = "abcdefghijklmnopqrstuvwxyz0123456789";
+    let mut buffer = Buffer::new::<256>();
+    buffer.write_nato(alphabet).unwrap();
+    assert_eq!(buffer.as_str().split(' ').count(), alphabet.len());
+
+    let mut buffer = Buffer::new::<9>();
+    assert!(buffer.write_nato("ab").is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_morse() {
+    let cases = [
+        ("", ""),
+        ("SOS 1", "... --- ... / .----"),
+        ("sos", "... --- ..."),
+        ("Hi!", ".... .."),
+        ("é✓?", ""),
+        ("a  b", ".- / / -..."),
+        ("09", "----- ----."),
+    ];
+
+    let mut buffer = Buffer::new::<32>();
+
+    for (s, expected) in cases {
+        buffer.clear();
+        buffer.write_morse(s).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{s:?}");
+    }
+
+    // every code is unique, so the output decodes unambiguously
+    let alphabet = "abcdefghijklmnopqrstuvwxyz0123456789";
+    let mut buffer = Buffer::new::<256>();
+    buffer.write_morse(alphabet).unwrap();
+    let codes: Vec<&str> = buffer.as_str().split(' ').collect();
+    assert_eq!(codes.len(), alphabet.len());
+
+    for code in &codes {
+        assert_eq!(codes.iter().filter(|other| *other == code).count(), 1);
+    }
+
+    // digits are five symbols, dots first up to 5 and dashes first after it
+    for (d, code) in codes[26..].iter().enumerate() {
+        let expected = if d <= 5 {
+            ".".repeat(d) + &"-".repeat(5 - d)
+        } else {
+            "-".repeat(d - 5) + &".".repeat(10 - d)
+        };
+        assert_eq!(*code, expected);
+    }
+
+    let mut buffer = Buffer::new::<6>();
+    assert!(buffer.write_morse("ab").is_err());
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,60 @@
+use super::Buffer;
+use crate::ByteBuffer;
+
+macro_rules! or_panic {
+    ($($ty:ty => $writefun:ident $panicfun:ident)*) => {$(
+        #[doc = concat!(
+            "Like [`", stringify!($writefun), "`](Self::", stringify!($writefun), "), ",
+            "but panics instead of failing if there isn't enough room.",
+        )]
+        #[track_caller]
+        pub const fn $panicfun(&mut self, value: $ty) {
+            if self.$writefun(value).is_err() {
+                panic!(concat!(
+                    "`",
+                    stringify!($writefun),
+                    "` failed, the buffer doesn't have enough room",
+                ));
+            }
+        }
+    )*};
+}
+
+impl<B: ByteBuffer> Buffer<B> {
+    or_panic! {
+        &str => push_str push_str_or_panic
+        char => write_char write_char_or_panic
+        u8 => write_u8 write_u8_or_panic
+        u16 => write_u16 write_u16_or_panic
+        u32 => write_u32 write_u32_or_panic
+        u64 => write_u64 write_u64_or_panic
+        u128 => write_u128 write_u128_or_panic
+        usize => write_usize write_usize_or_panic
+        i8 => write_i8 write_i8_or_panic
+        i16 => write_i16 write_i16_or_panic
+        i32 => write_i32 write_i32_or_panic
+        i64 => write_i64 write_i64_or_panic
+        i128 => write_i128 write_i128_or_panic
+        isize => write_isize write_isize_or_panic
+    }
+}
+
+#[test]
+fn test_or_panic() {
+    const LABEL: Buffer<[u8; 16]> = {
+        let mut buffer = Buffer::new::<16>();
+        buffer.push_str_or_panic("id=");
+        buffer.write_u32_or_panic(42);
+        buffer.write_char_or_panic(',');
+        buffer.write_i8_or_panic(-7);
+        buffer
+    };
+
+    assert_eq!(LABEL.as_str(), "id=42,-7");
+}
+
+#[test]
+#[should_panic = "`write_u32` failed, the buffer doesn't have enough room"]
+fn test_or_panic_full() {
+    Buffer::new::<4>().write_u32_or_panic(12_345);
+}
@@ -0,0 +1,576 @@
+use super::int::radix_len;
+use super::text::{char_count, char_prefix_len};
+use super::{Buffer, BufferWriteFailed, u64_digit_count};
+use crate::ByteBuffer;
+
+/// Where content goes within a wider field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    /// Centered, with the extra fill on the right if it can't be split evenly, like `{:^}`.
+    Center,
+}
+
+/// When to write a sign in front of a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    /// Only negative numbers get a sign.
+    Negative,
+    /// Non-negative numbers get a `+`, like `{:+}`.
+    Always,
+}
+
+/// Formatting options shared by the `write_*_with` methods, modeled on `core::fmt`'s
+/// format specs.
+///
+/// Start from [`FormatSpec::DEFAULT`] and override what's needed:
+/// `FormatSpec { width: 8, ..FormatSpec::DEFAULT }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatSpec {
+    /// The minimum width in characters.
+    pub width: usize,
+    pub fill: char,
+    /// `None` right aligns numbers and left aligns strings.
+    pub align: Option<Align>,
+    pub sign: Sign,
+    /// Pads numbers with zeros after the sign, ignoring `fill` and `align`, like `{:08}`.
+    /// Strings ignore this.
+    pub zero_pad: bool,
+    /// The maximum number of characters of a string to write. Integers ignore this.
+    pub precision: Option<usize>,
+    /// The radix of integers, from 2 to 36.
+    pub radix: u32,
+}
+
+impl FormatSpec {
+    /// Formats like `{}`: no padding, decimal, and a sign only for negative numbers.
+    pub const DEFAULT: Self = Self {
+        width: 0,
+        fill: ' ',
+        align: None,
+        sign: Sign::Negative,
+        zero_pad: false,
+        precision: None,
+        radix: 10,
+    };
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+// the fill characters around some content so that it fills a field, along with the
+// total length in bytes, so the fill can only be written after checking room for all of it
+pub(super) struct Padding {
+    fill: char,
+    before: usize,
+    after: usize,
+    len: usize,
+}
+
+impl Padding {
+    // `content_len` is the length of the content in bytes, and `content_width` in characters
+    pub(super) const fn new(
+        content_len: usize,
+        content_width: usize,
+        width: usize,
+        align: Align,
+        fill: char,
+    ) -> Self {
+        let pad = width.saturating_sub(content_width);
+
+        let (before, after) = match align {
+            Align::Left => (0, pad),
+            Align::Right => (pad, 0),
+            Align::Center => (pad / 2, pad - pad / 2),
+        };
+
+        Self::exact(content_len, before, after, fill)
+    }
+
+    // exactly `before` and `after` fill characters on each side
+    pub(super) const fn exact(content_len: usize, before: usize, after: usize, fill: char) -> Self {
+        // a huge width must not wrap around to something that looks like it fits
+        let pad = before.saturating_add(after);
+        let len = content_len.saturating_add(pad.saturating_mul(fill.len_utf8()));
+
+        Self {
+            fill,
+            before,
+            after,
+            len,
+        }
+    }
+
+    // the length of the content plus all of the fill, in bytes
+    pub(super) const fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<B: ByteBuffer> Buffer<B> {
+    // there must be room for `padding.len()` bytes, counting everything written since
+    // the check
+    pub(super) const unsafe fn push_fill_before(&mut self, padding: &Padding) {
+        unsafe { self.push_fill_unchecked(padding.fill, padding.before) }
+    }
+
+    // see `push_fill_before`
+    pub(super) const unsafe fn push_fill_after(&mut self, padding: &Padding) {
+        unsafe { self.push_fill_unchecked(padding.fill, padding.after) }
+    }
+
+    const unsafe fn push_fill_unchecked(&mut self, fill: char, count: usize) {
+        let mut buf = [0; 4];
+        let fill: &str = fill.encode_utf8(&mut buf);
+
+        let mut i = 0;
+
+        while i < count {
+            unsafe { self.push_str_unchecked(fill) };
+            i += 1;
+        }
+    }
+
+    /// Writes `value` in decimal, padded with `fill` to at least `width` characters.
+    ///
+    /// The sign counts towards the width. If `fill` is `'0'` and the value is right
+    /// aligned, the zeros go between the sign and the digits, like `{:08}` (`-0000042`).
+    pub const fn write_i64_field(
+        &mut self,
+        value: i64,
+        width: usize,
+        align: Align,
+        fill: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let negative = value < 0;
+        let magnitude = value.unsigned_abs();
+        let len = negative as usize + u64_digit_count(magnitude);
+        let padding = Padding::new(len, len, width, align, fill);
+
+        if padding.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let sign_aware = fill == '0' && matches!(align, Align::Right);
+
+        unsafe {
+            if !sign_aware {
+                self.push_fill_before(&padding);
+            }
+
+            if negative {
+                self.push_byte_unchecked(b'-');
+            }
+
+            if sign_aware {
+                self.push_fill_before(&padding);
+            }
+        }
+
+        tri!(self.write_u64(magnitude));
+        unsafe { self.push_fill_after(&padding) };
+
+        Ok(())
+    }
+
+    /// Writes `value` centered in a field of `width` characters, with the extra `fill` on
+    /// the right if the padding can't be split evenly, like `{:^}`.
+    pub const fn write_u64_centered(
+        &mut self,
+        value: u64,
+        width: usize,
+        fill: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let len = u64_digit_count(value);
+        let padding = Padding::new(len, len, width, Align::Center, fill);
+
+        if padding.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe { self.push_fill_before(&padding) };
+        tri!(self.write_u64(value));
+        unsafe { self.push_fill_after(&padding) };
+
+        Ok(())
+    }
+
+    /// Writes `value` as described by `spec`.
+    ///
+    /// # Panics
+    ///
+    /// If `spec.radix` is not in `2..=36`.
+    pub const fn write_u64_with(
+        &mut self,
+        value: u64,
+        spec: FormatSpec,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_int_with(false, value, spec)
+    }
+
+    /// Writes `value` as described by `spec`.
+    ///
+    /// # Panics
+    ///
+    /// If `spec.radix` is not in `2..=36`.
+    pub const fn write_i64_with(
+        &mut self,
+        value: i64,
+        spec: FormatSpec,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_int_with(value < 0, value.unsigned_abs(), spec)
+    }
+
+    /// Writes `s` as described by `spec`, truncated to `spec.precision` characters.
+    pub const fn write_str_with(
+        &mut self,
+        s: &str,
+        spec: FormatSpec,
+    ) -> Result<(), BufferWriteFailed> {
+        let s = match spec.precision {
+            Some(precision) => s.split_at(char_prefix_len(s, precision)).0,
+            None => s,
+        };
+
+        let align = match spec.align {
+            Some(align) => align,
+            None => Align::Left,
+        };
+
+        let padding = Padding::new(s.len(), char_count(s), spec.width, align, spec.fill);
+
+        if padding.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_fill_before(&padding);
+            self.push_str_unchecked(s);
+            self.push_fill_after(&padding);
+        }
+
+        Ok(())
+    }
+
+    /// Writes exactly `left` copies of `fill`, then `s`, then `right` copies of `fill`.
+    pub const fn write_str_padded(
+        &mut self,
+        s: &str,
+        left: usize,
+        right: usize,
+        fill: char,
+    ) -> Result<(), BufferWriteFailed> {
+        let padding = Padding::exact(s.len(), left, right, fill);
+
+        if padding.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_fill_before(&padding);
+            self.push_str_unchecked(s);
+            self.push_fill_after(&padding);
+        }
+
+        Ok(())
+    }
+
+    const fn write_int_with(
+        &mut self,
+        negative: bool,
+        magnitude: u64,
+        spec: FormatSpec,
+    ) -> Result<(), BufferWriteFailed> {
+        assert!(matches!(spec.radix, 2..=36), "radix must be in 2..=36");
+
+        let sign = match (negative, spec.sign) {
+            (true, _) => "-",
+            (false, Sign::Always) => "+",
+            (false, Sign::Negative) => "",
+        };
+
+        let digits = radix_len(magnitude, spec.radix);
+        let len = sign.len() + digits;
+
+        let padding = if spec.zero_pad {
+            Padding::new(len, len, spec.width, Align::Right, '0')
+        } else {
+            let align = match spec.align {
+                Some(align) => align,
+                None => Align::Right,
+            };
+
+            Padding::new(len, len, spec.width, align, spec.fill)
+        };
+
+        if padding.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            if !spec.zero_pad {
+                self.push_fill_before(&padding);
+            }
+
+            self.push_str_unchecked(sign);
+
+            if spec.zero_pad {
+                self.push_fill_before(&padding);
+            }
+
+            if spec.radix == 10 {
+                tri!(self.write_u64(magnitude));
+            } else {
+                self.push_u64_radix_unchecked(magnitude, spec.radix, digits);
+            }
+
+            self.push_fill_after(&padding);
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_i64_field() {
+    let cases: &[(i64, usize, Align, char, &str)] = &[
+        (42, 6, Align::Right, ' ', "    42"),
+        (-42, 6, Align::Right, ' ', "   -42"),
+        (-42, 6, Align::Left, ' ', "-42   "),
+        (-42, 6, Align::Center, '*', "*-42**"),
+        (-42, 6, Align::Right, '0', "-00042"),
+        (42, 6, Align::Right, '0', "000042"),
+        (-42, 6, Align::Left, '0', "-42000"),
+        (12345, 3, Align::Right, ' ', "12345"),
+        (i64::MIN, 0, Align::Right, ' ', "-9223372036854775808"),
+        (7, 3, Align::Center, '·', "·7·"),
+    ];
+
+    for &(value, width, align, fill, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.write_i64_field(value, width, align, fill).unwrap();
+        assert_eq!(
+            buffer.as_str(),
+            expected,
+            "{value} {width} {align:?} {fill:?}"
+        );
+    }
+
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_i64_field(-42, 6, Align::Right, '0').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_i64_field_huge_width() {
+    let mut buffer = Buffer::new::<64>();
+
+    for width in [(1 << 62) + 10, usize::MAX / 4 + 1, usize::MAX] {
+        for align in [Align::Left, Align::Right, Align::Center] {
+            assert!(buffer.write_i64_field(1, width, align, '🦀').is_err());
+            assert!(buffer.write_i64_field(-1, width, align, '0').is_err());
+        }
+    }
+
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_u64_centered() {
+    let mut buffer = Buffer::new::<128>();
+
+    for value in [0, 7, 42, 123, 12_345, u64::MAX] {
+        for width in [0, 1, 4, 5, 8, 25] {
+            for fill in [' ', '*', '─'] {
+                buffer.clear();
+                buffer.write_u64_centered(value, width, fill).unwrap();
+
+                let expected = format!("{value:^width$}");
+                assert_eq!(buffer.as_str(), expected.replace(' ', &fill.to_string()));
+            }
+        }
+    }
+
+    buffer.clear();
+    buffer.write_u64_centered(7, 4, '.').unwrap();
+    assert_eq!(buffer.as_str(), ".7..");
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(buffer.write_u64_centered(7, 5, ' ').is_err());
+    assert!(buffer.write_u64_centered(7, usize::MAX, ' ').is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_str_padded() {
+    let mut buffer = Buffer::new::<32>();
+    buffer.write_str_padded("title", 1, 4, '─').unwrap();
+    assert_eq!(buffer.as_str(), "─title────");
+
+    buffer.clear();
+    buffer.write_str_padded("", 2, 0, '*').unwrap();
+    buffer.write_str_padded("é", 0, 0, ' ').unwrap();
+    assert_eq!(buffer.as_str(), "**é");
+
+    // 8 bytes are needed
+    let mut buffer = Buffer::new::<7>();
+    assert!(buffer.write_str_padded("ab", 1, 1, '─').is_err());
+    assert!(buffer.write_str_padded("ab", usize::MAX, 1, ' ').is_err());
+    assert!(
+        buffer
+            .write_str_padded("ab", usize::MAX / 2, usize::MAX / 2, '─')
+            .is_err()
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_format_spec() {
+    const DEFAULT: FormatSpec = FormatSpec::DEFAULT;
+
+    let mut buffer = Buffer::new::<64>();
+    let mut check = |expected: &str, write: &dyn Fn(&mut Buffer<[u8; 64]>)| {
+        buffer.clear();
+        write(&mut buffer);
+        assert_eq!(buffer.as_str(), expected);
+    };
+
+    check(&format!("{:8}", 42), &|b| {
+        b.write_u64_with(
+            42,
+            FormatSpec {
+                width: 8,
+                ..DEFAULT
+            },
+        )
+        .unwrap()
+    });
+    check(&format!("{:+}", 0), &|b| {
+        b.write_i64_with(
+            0,
+            FormatSpec {
+                sign: Sign::Always,
+                ..DEFAULT
+            },
+        )
+        .unwrap()
+    });
+    check(&format!("{:+05}", -3), &|b| {
+        let spec = FormatSpec {
+            width: 5,
+            sign: Sign::Always,
+            zero_pad: true,
+            ..DEFAULT
+        };
+        b.write_i64_with(-3, spec).unwrap()
+    });
+    check(&format!("{:>08}", -42), &|b| {
+        let spec = FormatSpec {
+            width: 8,
+            align: Some(Align::Right),
+            zero_pad: true,
+            ..DEFAULT
+        };
+        b.write_i64_with(-42, spec).unwrap()
+    });
+    check(&format!("{:<+6}", 5), &|b| {
+        let spec = FormatSpec {
+            width: 6,
+            align: Some(Align::Left),
+            sign: Sign::Always,
+            ..DEFAULT
+        };
+        b.write_i64_with(5, spec).unwrap()
+    });
+    check(&format!("{:_^10x}", 255), &|b| {
+        let spec = FormatSpec {
+            width: 10,
+            fill: '_',
+            align: Some(Align::Center),
+            radix: 16,
+            ..DEFAULT
+        };
+        b.write_u64_with(255, spec).unwrap()
+    });
+    check(&format!("{:^7.2}", "hello"), &|b| {
+        let spec = FormatSpec {
+            width: 7,
+            align: Some(Align::Center),
+            precision: Some(2),
+            ..DEFAULT
+        };
+        b.write_str_with("hello", spec).unwrap()
+    });
+    check(&format!("{:05}", "ab"), &|b| {
+        b.write_str_with(
+            "ab",
+            FormatSpec {
+                width: 5,
+                zero_pad: true,
+                ..DEFAULT
+            },
+        )
+        .unwrap()
+    });
+    check(&format!("{:>4}", "é"), &|b| {
+        b.write_str_with(
+            "é",
+            FormatSpec {
+                width: 4,
+                align: Some(Align::Right),
+                ..DEFAULT
+            },
+        )
+        .unwrap()
+    });
+
+    let mut buffer = Buffer::new::<4>();
+    assert!(
+        buffer
+            .write_u64_with(
+                1,
+                FormatSpec {
+                    width: 5,
+                    ..DEFAULT
+                }
+            )
+            .is_err()
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_format_spec_huge_width() {
+    let mut buffer = Buffer::new::<64>();
+
+    for width in [(1 << 62) + 10, usize::MAX / 4 + 1, usize::MAX] {
+        for align in [None, Some(Align::Left), Some(Align::Center)] {
+            let spec = FormatSpec {
+                width,
+                fill: '🦀',
+                align,
+                ..FormatSpec::DEFAULT
+            };
+
+            assert!(buffer.write_str_with("a", spec).is_err());
+            assert!(buffer.write_u64_with(1, spec).is_err());
+            assert!(
+                buffer
+                    .write_i64_with(
+                        -1,
+                        FormatSpec {
+                            zero_pad: true,
+                            ..spec
+                        }
+                    )
+                    .is_err()
+            );
+        }
+    }
+
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,148 @@
+use super::Buffer;
+use crate::ByteBuffer;
+
+/// Why the written content couldn't be parsed as an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// There were no digits at all.
+    Empty,
+    /// Something other than a leading sign or an ASCII digit was found.
+    InvalidDigit,
+    /// The number doesn't fit in the integer type.
+    Overflow,
+}
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Parses the written content as a decimal `u64`, like `str::parse` does: an optional
+    /// `+` followed by digits.
+    pub const fn parse_u64(&self) -> Result<u64, ParseError> {
+        let digits = match self.as_bytes() {
+            [b'+', rest @ ..] => rest,
+            bytes => bytes,
+        };
+
+        if self.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        parse_magnitude(digits, u64::MAX)
+    }
+
+    /// Parses the written content as a decimal `i64`, like `str::parse` does: an optional
+    /// `+` or `-` followed by digits.
+    pub const fn parse_i64(&self) -> Result<i64, ParseError> {
+        let (negative, digits) = match self.as_bytes() {
+            [b'-', rest @ ..] => (true, rest),
+            [b'+', rest @ ..] => (false, rest),
+            bytes => (false, bytes),
+        };
+
+        if self.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let max = if negative {
+            i64::MIN.unsigned_abs()
+        } else {
+            i64::MAX as u64
+        };
+
+        match parse_magnitude(digits, max) {
+            Ok(magnitude) if negative => Ok((magnitude as i64).wrapping_neg()),
+            Ok(magnitude) => Ok(magnitude as i64),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+// a lone sign has no digits, but isn't empty, so it's an invalid digit like in `std`
+const fn parse_magnitude(digits: &[u8], max: u64) -> Result<u64, ParseError> {
+    if digits.is_empty() {
+        return Err(ParseError::InvalidDigit);
+    }
+
+    let mut value: u64 = 0;
+    let mut i = 0;
+
+    while i < digits.len() {
+        let digit = match digits[i] {
+            byte @ b'0'..=b'9' => (byte - b'0') as u64,
+            _ => return Err(ParseError::InvalidDigit),
+        };
+
+        // like `std`, an overflow is reported even if an invalid digit follows
+        value = match value.checked_mul(10) {
+            Some(scaled) if scaled <= max - digit => scaled + digit,
+            _ => return Err(ParseError::Overflow),
+        };
+
+        i += 1;
+    }
+
+    Ok(value)
+}
+
+#[test]
+fn test_parse() {
+    use core::num::IntErrorKind;
+
+    fn kind(err: core::num::ParseIntError) -> ParseError {
+        match err.kind() {
+            IntErrorKind::Empty => ParseError::Empty,
+            IntErrorKind::InvalidDigit => ParseError::InvalidDigit,
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ParseError::Overflow,
+            kind => unreachable!("{kind:?}"),
+        }
+    }
+
+    let cases = [
+        "",
+        "0",
+        "+0",
+        "-0",
+        "42",
+        "+42",
+        "-42",
+        "+",
+        "-",
+        "--1",
+        "1_000",
+        " 1",
+        "é",
+        "007",
+        "9223372036854775807",
+        "9223372036854775808",
+        "-9223372036854775808",
+        "-9223372036854775809",
+        "18446744073709551615",
+        "18446744073709551616",
+        "99999999999999999999x",
+        "184467440737095516150",
+    ];
+
+    for s in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.push_str(s).unwrap();
+        assert_eq!(buffer.parse_u64(), s.parse::<u64>().map_err(kind), "{s:?}");
+        assert_eq!(buffer.parse_i64(), s.parse::<i64>().map_err(kind), "{s:?}");
+    }
+
+    const PARSED: Result<u64, ParseError> = {
+        let mut buffer = Buffer::new::<8>();
+        buffer.write_u32_or_panic(1234);
+        buffer.parse_u64()
+    };
+    assert_eq!(PARSED, Ok(1234));
+}
+
+#[cfg(kani)]
+#[kani::proof]
+#[kani::unwind(21)]
+fn prove_parse_i64() {
+    let x: i64 = kani::any();
+
+    let mut buffer = Buffer::<[u8; 20]>::create();
+    buffer.write_i64(x).unwrap();
+
+    assert_eq!(buffer.parse_i64(), Ok(x));
+}
@@ -0,0 +1,1182 @@
+use super::{Buffer, BufferWriteFailed, u64_digit_count};
+use crate::ByteBuffer;
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Counts the lines in the written content, like `self.as_str().lines().count()`.
+    pub const fn line_count(&self) -> usize {
+        let bytes = self.as_bytes();
+        let mut count = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            count += (bytes[i] == b'\n') as usize;
+            i += 1;
+        }
+
+        // a final line without a `\n` still counts
+        count + (!bytes.is_empty() && bytes[bytes.len() - 1] != b'\n') as usize
+    }
+
+    /// Returns the line at index `n`, like `self.as_str().lines().nth(n)`.
+    ///
+    /// Lines end in `\n` or `\r\n`, and the line ending isn't included.
+    pub const fn nth_line(&self, n: usize) -> Option<&str> {
+        let bytes = self.as_bytes();
+        let mut start = 0;
+        let mut line = 0;
+
+        while line < n {
+            while start < bytes.len() && bytes[start] != b'\n' {
+                start += 1;
+            }
+
+            start += 1;
+            line += 1;
+
+            if start >= bytes.len() {
+                return None;
+            }
+        }
+
+        if start >= bytes.len() {
+            return None;
+        }
+
+        let mut end = start;
+
+        while end < bytes.len() && bytes[end] != b'\n' {
+            end += 1;
+        }
+
+        if end < bytes.len() && end > start && bytes[end - 1] == b'\r' {
+            end -= 1;
+        }
+
+        let (before, _) = self.as_str().split_at(end);
+        Some(before.split_at(start).1)
+    }
+
+    /// Writes `text` with `indent` in front of each of its lines.
+    ///
+    /// Empty lines aren't indented, so no trailing whitespace is written, and a trailing
+    /// `\n` doesn't start a new line. Fails without writing anything if it doesn't fit.
+    pub const fn write_indented(
+        &mut self,
+        text: &str,
+        indent: &str,
+    ) -> Result<(), BufferWriteFailed> {
+        let bytes = text.as_bytes();
+        let mut lines = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let line_start = i == 0 || bytes[i - 1] == b'\n';
+            lines += (line_start && bytes[i] != b'\n') as usize;
+            i += 1;
+        }
+
+        if bytes
+            .len()
+            .saturating_add(lines.saturating_mul(indent.len()))
+            > self.remaining_capacity()
+        {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut start = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i] == b'\n' {
+                if i > start {
+                    unsafe { self.push_str_unchecked(indent) };
+                }
+
+                // `\n` is ASCII, so these splits are on char boundaries
+                let (line, _) = text.split_at(i + 1);
+                unsafe { self.push_str_unchecked(line.split_at(start).1) };
+                start = i + 1;
+            }
+
+            i += 1;
+        }
+
+        if start < bytes.len() {
+            unsafe {
+                self.push_str_unchecked(indent);
+                self.push_str_unchecked(text.split_at(start).1);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes `items` as a numbered list, counting up from `start` with `sep` between the
+    /// entries, so `write_numbered(&["a", "b"], 1, "\n")` writes `1. a\n2. b`.
+    ///
+    /// Fails without writing anything if the whole list doesn't fit.
+    ///
+    /// # Panics
+    ///
+    /// If the number of the last item overflows a `u64`.
+    pub const fn write_numbered(
+        &mut self,
+        items: &[&str],
+        start: u64,
+        sep: &str,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_numbered_imp(items, start, sep, false)
+    }
+
+    /// Like [`write_numbered`](Self::write_numbered), but the numbers are right-aligned
+    /// with spaces to the width of the largest one, so the items line up.
+    ///
+    /// # Panics
+    ///
+    /// If the number of the last item overflows a `u64`.
+    pub const fn write_numbered_aligned(
+        &mut self,
+        items: &[&str],
+        start: u64,
+        sep: &str,
+    ) -> Result<(), BufferWriteFailed> {
+        self.write_numbered_imp(items, start, sep, true)
+    }
+
+    const fn write_numbered_imp(
+        &mut self,
+        items: &[&str],
+        start: u64,
+        sep: &str,
+        align: bool,
+    ) -> Result<(), BufferWriteFailed> {
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        let Some(last) = start.checked_add(items.len() as u64 - 1) else {
+            panic!("the numbers of `items` overflow a `u64`")
+        };
+        let width = u64_digit_count(last);
+        let mut len = sep.len().saturating_mul(items.len() - 1);
+        let mut i = 0;
+
+        while i < items.len() {
+            let number_len = if align {
+                width
+            } else {
+                u64_digit_count(start + i as u64)
+            };
+
+            len = len
+                .saturating_add(number_len + ". ".len())
+                .saturating_add(items[i].len());
+            i += 1;
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < items.len() {
+            let number = start + i as u64;
+
+            unsafe {
+                if i != 0 {
+                    self.push_str_unchecked(sep);
+                }
+
+                if align {
+                    let mut pad = width - u64_digit_count(number);
+
+                    while pad > 0 {
+                        self.push_byte_unchecked(b' ');
+                        pad -= 1;
+                    }
+                }
+
+                // can't fail, there is room
+                let _ = self.write_u64(number);
+                self.push_str_unchecked(". ");
+                self.push_str_unchecked(items[i]);
+            }
+
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Converts the written content to ASCII upper case in place.
+    ///
+    /// Non-ASCII characters are left untouched.
+    pub const fn make_ascii_uppercase(&mut self) {
+        self.as_mut_str().make_ascii_uppercase();
+    }
+
+    /// Converts the written content to ASCII lower case in place.
+    ///
+    /// Non-ASCII characters are left untouched.
+    pub const fn make_ascii_lowercase(&mut self) {
+        self.as_mut_str().make_ascii_lowercase();
+    }
+
+    /// Replaces every `from` byte in the written content with `to`, in place.
+    ///
+    /// # Panics
+    ///
+    /// If `from` or `to` isn't ASCII, since that could break UTF-8.
+    pub const fn replace_ascii(&mut self, from: u8, to: u8) {
+        assert!(
+            from.is_ascii() && to.is_ascii(),
+            "only ASCII bytes can be replaced"
+        );
+
+        let len = self.len();
+        // ASCII bytes are never part of a multi-byte character, so this stays UTF-8
+        let bytes = unsafe { core::slice::from_raw_parts_mut(self.as_mut_ptr(), len) };
+        let mut i = 0;
+
+        while i < len {
+            if bytes[i] == from {
+                bytes[i] = to;
+            }
+
+            i += 1;
+        }
+    }
+
+    /// Removes every ASCII byte of the written content for which `keep` returns `false`,
+    /// shifting the rest left in place.
+    ///
+    /// Non-ASCII characters are always kept, so the content stays UTF-8. This isn't `const`
+    /// because function pointers can't be called in a `const fn`.
+    pub fn retain_ascii(&mut self, keep: fn(u8) -> bool) {
+        let len = self.len();
+        // only whole ASCII bytes are removed, so this stays UTF-8
+        let bytes = unsafe { core::slice::from_raw_parts_mut(self.as_mut_ptr(), len) };
+        let mut kept = 0;
+
+        for i in 0..len {
+            let byte = bytes[i];
+
+            if !byte.is_ascii() || keep(byte) {
+                bytes[kept] = byte;
+                kept += 1;
+            }
+        }
+
+        self.len = kept;
+    }
+
+    /// Replaces every run of ASCII whitespace in the written content with a single space,
+    /// shifting the rest left in place.
+    ///
+    /// Runs of one are replaced too, so a lone `\n` becomes a space.
+    pub const fn dedup_ascii_whitespace(&mut self) {
+        let len = self.len();
+        // whitespace is ASCII, and is replaced with ASCII, so this stays UTF-8
+        let bytes = unsafe { core::slice::from_raw_parts_mut(self.as_mut_ptr(), len) };
+        let mut kept = 0;
+        let mut in_run = false;
+        let mut i = 0;
+
+        while i < len {
+            let byte = bytes[i];
+            let space = byte.is_ascii_whitespace();
+
+            if !(space && in_run) {
+                bytes[kept] = if space { b' ' } else { byte };
+                kept += 1;
+            }
+
+            in_run = space;
+            i += 1;
+        }
+
+        self.len = kept;
+    }
+
+    /// Removes trailing ASCII whitespace from the written content, like
+    /// [`str::trim_ascii_end`].
+    pub const fn trim_end_ascii(&mut self) {
+        let bytes = self.as_bytes();
+        let mut len = bytes.len();
+
+        // whitespace is ASCII, so this only ever cuts between characters
+        while len > 0 && bytes[len - 1].is_ascii_whitespace() {
+            len -= 1;
+        }
+
+        self.len = len;
+    }
+
+    /// Removes every trailing `byte` from the written content, so trimming `b','` from
+    /// `a,b,,` leaves `a,b`.
+    ///
+    /// # Panics
+    ///
+    /// If `byte` isn't ASCII, since that could break UTF-8.
+    pub const fn trim_end_matches_ascii(&mut self, byte: u8) {
+        assert!(byte.is_ascii(), "only ASCII bytes can be trimmed");
+
+        let bytes = self.as_bytes();
+        let mut len = bytes.len();
+
+        while len > 0 && bytes[len - 1] == byte {
+            len -= 1;
+        }
+
+        self.len = len;
+    }
+
+    /// Writes at most `max_chars` characters from the start of `s`, like `{:.N}`.
+    ///
+    /// Fails without writing anything if the truncated string doesn't fit.
+    pub const fn write_str_truncated(
+        &mut self,
+        s: &str,
+        max_chars: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        let (s, _) = s.split_at(char_prefix_len(s, max_chars));
+        self.push_str(s)
+    }
+
+    /// Writes `s` if it has at most `max_width` characters, and otherwise its first
+    /// `max_width - 1` characters followed by `…`, so that `max_width` characters are
+    /// written either way. A `max_width` of zero writes nothing.
+    ///
+    /// Fails without writing anything if it doesn't fit.
+    pub const fn write_str_ellipsized(
+        &mut self,
+        s: &str,
+        max_width: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        if max_width == 0 {
+            return Ok(());
+        }
+
+        let prefix_len = char_prefix_len(s, max_width);
+
+        if prefix_len == s.len() {
+            return self.push_str(s);
+        }
+
+        // the cut is after a char, so it's on a char boundary
+        let (prefix, _) = s.split_at(char_prefix_len(s, max_width - 1));
+
+        if prefix.len() + '…'.len_utf8() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            self.push_str_unchecked(prefix);
+            self.push_str_unchecked("…");
+        }
+
+        Ok(())
+    }
+
+    /// Writes `pattern` repeatedly, cut off so that exactly `width` characters are written,
+    /// like `-=-=-` for the pattern `-=` and a width of 5.
+    ///
+    /// Fails without writing anything if it doesn't fit.
+    ///
+    /// # Panics
+    ///
+    /// If `pattern` is empty and `width` isn't zero.
+    pub const fn write_pattern(
+        &mut self,
+        pattern: &str,
+        width: usize,
+    ) -> Result<(), BufferWriteFailed> {
+        if width == 0 {
+            return Ok(());
+        }
+
+        assert!(!pattern.is_empty(), "an empty pattern can't fill a width");
+
+        let pattern_chars = char_count(pattern);
+        let repeats = width / pattern_chars;
+        let (tail, _) = pattern.split_at(char_prefix_len(pattern, width % pattern_chars));
+
+        if pattern
+            .len()
+            .saturating_mul(repeats)
+            .saturating_add(tail.len())
+            > self.remaining_capacity()
+        {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < repeats {
+            unsafe { self.push_str_unchecked(pattern) };
+            i += 1;
+        }
+
+        unsafe { self.push_str_unchecked(tail) };
+
+        Ok(())
+    }
+
+    /// Writes as much of `s` as fits, stopping at a character boundary.
+    ///
+    /// Returns how many bytes of `s` were written and whether that was all of it, so the
+    /// rest can be written after making room.
+    pub const fn try_push_str(&mut self, s: &str) -> (usize, bool) {
+        let bytes = s.as_bytes();
+        let mut written = if s.len() < self.remaining_capacity() {
+            s.len()
+        } else {
+            self.remaining_capacity()
+        };
+
+        while written < bytes.len() && is_utf8_continuation(bytes[written]) {
+            written -= 1;
+        }
+
+        unsafe { self.push_str_unchecked(s.split_at(written).0) };
+
+        (written, written == s.len())
+    }
+
+    /// Writes `c` if it fits and returns whether it did, leaving the buffer unchanged
+    /// otherwise.
+    pub const fn write_char_lossy(&mut self, c: char) -> bool {
+        self.write_char(c).is_ok()
+    }
+
+    /// Writes `if_true` or `if_false` depending on `value`, like `on`/`off` or `yes`/`no`.
+    pub const fn write_bool_as(
+        &mut self,
+        value: bool,
+        if_true: &str,
+        if_false: &str,
+    ) -> Result<(), BufferWriteFailed> {
+        self.push_str(if value { if_true } else { if_false })
+    }
+
+    /// Writes `value` as a single `1` or `0`.
+    pub const fn write_bool_bit(&mut self, value: bool) -> Result<(), BufferWriteFailed> {
+        self.push_str(if value { "1" } else { "0" })
+    }
+
+    /// Reverses the order of the written characters in place.
+    ///
+    /// This works on `char`s, so multi-byte characters stay intact, but characters that
+    /// combine with their neighbours (like accents written as combining marks) won't.
+    pub const fn reverse(&mut self) {
+        let len = self.len();
+        // the bytes are only out of order between the two passes
+        let bytes = unsafe { core::slice::from_raw_parts_mut(self.as_mut_ptr(), len) };
+
+        reverse_bytes(bytes, 0, len);
+
+        // each multi-byte character is now backwards, with its continuation bytes first
+        let mut i = 0;
+
+        while i < len {
+            let start = i;
+
+            while is_utf8_continuation(bytes[i]) {
+                i += 1;
+            }
+
+            i += 1;
+            reverse_bytes(bytes, start, i);
+        }
+    }
+
+    /// Inserts `c` at byte index `idx`, moving everything after it to the right, like
+    /// `String::insert`.
+    ///
+    /// # Panics
+    ///
+    /// If `idx` isn't a char boundary of the written content.
+    pub const fn insert_char(&mut self, idx: usize, c: char) -> Result<(), BufferWriteFailed> {
+        assert!(self.is_char_boundary(idx), "`idx` is not a char boundary");
+
+        let mut utf8 = [0; 4];
+        let encoded = c.encode_utf8(&mut utf8).as_bytes();
+
+        if encoded.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        unsafe {
+            let ptr = self.as_mut_ptr().add(idx);
+            ptr.copy_to(ptr.add(encoded.len()), self.len - idx);
+            ptr.copy_from_nonoverlapping(encoded.as_ptr(), encoded.len());
+        }
+
+        self.len += encoded.len();
+        Ok(())
+    }
+
+    pub fn chars(&self) -> core::str::Chars<'_> {
+        self.as_str().chars()
+    }
+
+    pub fn char_indices(&self) -> core::str::CharIndices<'_> {
+        self.as_str().char_indices()
+    }
+
+    /// Counts the characters in the written content, like `self.chars().count()` but usable
+    /// in `const` code.
+    ///
+    /// This is the number of `char`s, not the display width, so combining marks and wide
+    /// characters still count as one each.
+    pub const fn char_count(&self) -> usize {
+        char_count(self.as_str())
+    }
+
+    /// Returns whether the written content is exactly `other`, like `==` but usable in
+    /// `const` code.
+    pub const fn eq_str(&self, other: &str) -> bool {
+        bytes_eq(self.as_bytes(), other.as_bytes())
+    }
+
+    pub const fn starts_with(&self, prefix: &str) -> bool {
+        let bytes = self.as_str().as_bytes();
+        bytes.len() >= prefix.len() && bytes_eq(bytes.split_at(prefix.len()).0, prefix.as_bytes())
+    }
+
+    pub const fn ends_with(&self, suffix: &str) -> bool {
+        let bytes = self.as_str().as_bytes();
+        bytes.len() >= suffix.len()
+            && bytes_eq(
+                bytes.split_at(bytes.len() - suffix.len()).1,
+                suffix.as_bytes(),
+            )
+    }
+
+    pub const fn contains(&self, needle: &str) -> bool {
+        self.find(needle).is_some()
+    }
+
+    /// Returns the byte index of the first occurrence of `needle` in the written content.
+    ///
+    /// Like [`str::find`], an empty `needle` is found at index `0`.
+    pub const fn find(&self, needle: &str) -> Option<usize> {
+        find_bytes(self.as_str().as_bytes(), needle.as_bytes())
+    }
+
+    /// Returns whether `idx` is at the start or end of a character in the written content,
+    /// like [`str::is_char_boundary`].
+    pub const fn is_char_boundary(&self, idx: usize) -> bool {
+        let bytes = self.as_bytes();
+
+        if idx >= bytes.len() {
+            return idx == bytes.len();
+        }
+
+        !is_utf8_continuation(bytes[idx])
+    }
+
+    /// Returns the closest char boundary at or before `idx`, clamped to the length of the
+    /// written content.
+    pub const fn floor_char_boundary(&self, idx: usize) -> usize {
+        let bytes = self.as_bytes();
+
+        if idx >= bytes.len() {
+            return bytes.len();
+        }
+
+        let mut idx = idx;
+
+        while is_utf8_continuation(bytes[idx]) {
+            idx -= 1;
+        }
+
+        idx
+    }
+}
+
+pub(super) const fn char_count(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        count += !is_utf8_continuation(bytes[i]) as usize;
+        i += 1;
+    }
+
+    count
+}
+
+// the byte length of the first `max_chars` characters of `s`
+pub(super) const fn char_prefix_len(s: &str, max_chars: usize) -> usize {
+    let bytes = s.as_bytes();
+    let mut chars = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !is_utf8_continuation(bytes[i]) {
+            if chars == max_chars {
+                return i;
+            }
+
+            chars += 1;
+        }
+
+        i += 1;
+    }
+
+    bytes.len()
+}
+
+const fn reverse_bytes(bytes: &mut [u8], start: usize, end: usize) {
+    let mut lo = start;
+    let mut hi = end;
+
+    while lo + 1 < hi {
+        hi -= 1;
+        bytes.swap(lo, hi);
+        lo += 1;
+    }
+}
+
+const fn is_utf8_continuation(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+const fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+
+    let mut start = 0;
+
+    while start + needle.len() <= haystack.len() {
+        let (_, window) = haystack.split_at(start);
+
+        if bytes_eq(window.split_at(needle.len()).0, needle) {
+            return Some(start);
+        }
+
+        start += 1;
+    }
+
+    None
+}
+
+#[test]
+fn test_ascii_case() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.push_str("Hello, wörld!").unwrap();
+
+    buffer.make_ascii_uppercase();
+    assert_eq!(buffer.as_str(), "HELLO, WöRLD!");
+
+    buffer.make_ascii_lowercase();
+    assert_eq!(buffer.as_str(), "hello, wörld!");
+}
+
+#[test]
+fn test_replace_ascii() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.replace_ascii(b',', b';');
+
+    buffer.push_str("a,é,,b").unwrap();
+    buffer.replace_ascii(b',', b';');
+    assert_eq!(buffer.as_str(), "a;é;;b");
+
+    buffer.replace_ascii(b'x', b'y');
+    assert_eq!(buffer.as_str(), "a;é;;b");
+}
+
+#[test]
+fn test_trim_end_ascii() {
+    let cases = [
+        ("", ""),
+        ("   ", ""),
+        ("cell  \t\r\n", "cell"),
+        ("  a b  ", "  a b"),
+        ("é\u{a0}", "é\u{a0}"),
+    ];
+
+    for (input, expected) in cases {
+        let mut buffer = Buffer::new::<16>();
+        buffer.push_str(input).unwrap();
+        buffer.trim_end_ascii();
+        assert_eq!(buffer.as_str(), expected, "{input:?}");
+    }
+
+    const TRIMMED: Buffer<[u8; 8]> = {
+        let mut buffer = Buffer::new::<8>();
+        buffer.push_str_or_panic("a,b,,");
+        buffer.trim_end_matches_ascii(b',');
+        buffer
+    };
+    assert_eq!(TRIMMED.as_str(), "a,b");
+
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("é00").unwrap();
+    buffer.trim_end_matches_ascii(b'0');
+    assert_eq!(buffer.as_str(), "é");
+    buffer.trim_end_matches_ascii(b'0');
+    assert_eq!(buffer.as_str(), "é");
+}
+
+#[test]
+#[should_panic = "only ASCII bytes"]
+fn test_trim_end_matches_non_ascii() {
+    let mut buffer = Buffer::new::<4>();
+    buffer.push_str("é").unwrap();
+    buffer.trim_end_matches_ascii(0xa9);
+}
+
+#[test]
+fn test_dedup_ascii_whitespace() {
+    let cases = [
+        ("", ""),
+        ("a b", "a b"),
+        ("  lots   of\t\t space\n\n", " lots of space "),
+        ("é \r\n ü", "é ü"),
+        ("\u{a0}\u{a0}", "\u{a0}\u{a0}"),
+    ];
+
+    for (input, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.push_str(input).unwrap();
+        buffer.dedup_ascii_whitespace();
+        assert_eq!(buffer.as_str(), expected, "{input:?}");
+    }
+
+    const DEDUPED: Buffer<[u8; 8]> = {
+        let mut buffer = Buffer::new::<8>();
+        buffer.push_str_or_panic("a    b");
+        buffer.dedup_ascii_whitespace();
+        buffer
+    };
+    assert_eq!(DEDUPED.as_str(), "a b");
+}
+
+#[test]
+fn test_retain_ascii() {
+    let mut buffer = Buffer::new::<32>();
+    buffer.retain_ascii(|_| false);
+    assert!(buffer.is_empty());
+
+    buffer.push_str(" my fancy  idént ").unwrap();
+    buffer.retain_ascii(|byte| byte != b' ');
+    assert_eq!(buffer.as_str(), "myfancyidént");
+
+    // non-ASCII characters survive even a predicate that rejects everything
+    buffer.retain_ascii(|_| false);
+    assert_eq!(buffer.as_str(), "é");
+
+    buffer.push_str("a1b2").unwrap();
+    buffer.retain_ascii(|byte| byte.is_ascii_digit());
+    assert_eq!(buffer.as_str(), "é12");
+    assert_eq!(buffer.remaining_capacity(), 28);
+}
+
+#[test]
+#[should_panic = "only ASCII bytes"]
+fn test_replace_non_ascii() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.push_str("é").unwrap();
+    buffer.replace_ascii(0xa9, b'e');
+}
+
+#[test]
+fn test_str_truncated() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.write_str_truncated("héllo", 2).unwrap();
+    assert_eq!(buffer.as_str(), "hé");
+
+    buffer.write_str_truncated("abc", 0).unwrap();
+    buffer.write_str_truncated("abc", 10).unwrap();
+    assert_eq!(buffer.as_str(), "héabc");
+
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_str_truncated("日本", 2).is_err());
+    buffer.write_str_truncated("日本", 1).unwrap();
+    assert_eq!(buffer.as_str(), "日");
+}
+
+#[test]
+fn test_str_ellipsized() {
+    let cases = [
+        ("verylongtext!", 11, "verylongte…"),
+        ("verylongte…", 11, "verylongte…"),
+        ("short", 11, "short"),
+        ("exact", 5, "exact"),
+        ("toolong", 1, "…"),
+        ("toolong", 0, ""),
+        ("", 0, ""),
+        ("日本語テキスト", 4, "日本語…"),
+        ("héllo", 2, "h…"),
+    ];
+
+    for (s, max_width, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.write_str_ellipsized(s, max_width).unwrap();
+        assert_eq!(buffer.as_str(), expected, "{s:?} {max_width}");
+        assert!(buffer.char_count() <= max_width);
+    }
+
+    // the ellipsis is 3 bytes
+    let mut buffer = Buffer::new::<5>();
+    assert!(buffer.write_str_ellipsized("abcdef", 4).is_err());
+    assert!(buffer.is_empty());
+    buffer.write_str_ellipsized("abcdef", 3).unwrap();
+    assert_eq!(buffer.as_str(), "ab…");
+}
+
+#[test]
+fn test_pattern() {
+    let cases = [
+        ("-=", 5, "-=-=-"),
+        ("-=", 6, "-=-=-="),
+        ("-", 3, "---"),
+        ("─┼", 3, "─┼─"),
+        ("abc", 2, "ab"),
+        ("abc", 0, ""),
+        ("", 0, ""),
+    ];
+
+    for (pattern, width, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.write_pattern(pattern, width).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<8>();
+    assert!(buffer.write_pattern("─", 3).is_err());
+    assert!(buffer.write_pattern("-", usize::MAX).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+#[should_panic = "an empty pattern"]
+fn test_empty_pattern() {
+    let _ = Buffer::new::<8>().write_pattern("", 1);
+}
+
+#[test]
+fn test_try_push_str() {
+    let mut buffer = Buffer::new::<4>();
+    assert_eq!(buffer.try_push_str("ab"), (2, true));
+    assert_eq!(buffer.try_push_str("cdef"), (2, false));
+    assert_eq!(buffer.as_str(), "abcd");
+    assert_eq!(buffer.try_push_str("x"), (0, false));
+    assert_eq!(buffer.try_push_str(""), (0, true));
+
+    let mut out = String::new();
+    let mut rest = "aé✓🦀";
+    let mut buffer = Buffer::new::<5>();
+
+    loop {
+        let (written, done) = buffer.try_push_str(rest);
+        rest = &rest[written..];
+        out.push_str(buffer.as_str());
+        buffer.clear();
+
+        if done {
+            break;
+        }
+    }
+
+    assert_eq!(out, "aé✓🦀");
+}
+
+#[test]
+fn test_char_lossy() {
+    let mut buffer = Buffer::new::<3>();
+    assert!(buffer.write_char_lossy('a'));
+    assert!(!buffer.write_char_lossy('✓'));
+    assert!(buffer.write_char_lossy('é'));
+    assert!(!buffer.write_char_lossy('b'));
+    assert_eq!(buffer.as_str(), "aé");
+}
+
+#[test]
+fn test_bool_as() {
+    let mut buffer = Buffer::new::<8>();
+    buffer.write_bool_as(true, "on", "off").unwrap();
+    buffer.write_bool_as(false, "on", "off").unwrap();
+    assert_eq!(buffer.as_str(), "onoff");
+
+    assert!(buffer.write_bool_as(false, "yes", "no!!").is_err());
+    buffer.write_bool_as(true, "yes", "no!!").unwrap();
+    assert_eq!(buffer.as_str(), "onoffyes");
+}
+
+#[test]
+fn test_indented() {
+    let cases = [
+        ("", ""),
+        ("a", "    a"),
+        ("a\nb", "    a\n    b"),
+        ("a\nb\n", "    a\n    b\n"),
+        ("\na\n\nb", "\n    a\n\n    b"),
+        ("\n\n", "\n\n"),
+        ("fn f() {\r\n}\r\n", "    fn f() {\r\n    }\r\n"),
+    ];
+
+    for (text, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.write_indented(text, "    ").unwrap();
+        assert_eq!(buffer.as_str(), expected, "{text:?}");
+    }
+
+    let mut buffer = Buffer::new::<10>();
+    assert!(buffer.write_indented("a\nb", "    ").is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_numbered() {
+    let mut buffer = Buffer::new::<32>();
+    buffer
+        .write_numbered(&["first", "second"], 1, "\n")
+        .unwrap();
+    assert_eq!(buffer.as_str(), "1. first\n2. second");
+
+    buffer.clear();
+    buffer.write_numbered(&[], 1, "\n").unwrap();
+    buffer.write_numbered(&["a", "b", "c"], 9, ", ").unwrap();
+    assert_eq!(buffer.as_str(), "9. a, 10. b, 11. c");
+
+    buffer.clear();
+    buffer
+        .write_numbered_aligned(&["a", "b", "c"], 9, "\n")
+        .unwrap();
+    assert_eq!(buffer.as_str(), " 9. a\n10. b\n11. c");
+
+    buffer.clear();
+    buffer.write_numbered(&["max"], u64::MAX, "").unwrap();
+    assert_eq!(buffer.as_str(), "18446744073709551615. max");
+
+    let mut buffer = Buffer::new::<10>();
+    assert!(buffer.write_numbered(&["a", "b"], 1, " | ").is_err());
+    assert!(buffer.write_numbered_aligned(&["a", "b"], 9, "|").is_err());
+    assert!(buffer.is_empty());
+    buffer.write_numbered(&["a", "b"], 1, "|").unwrap();
+    assert_eq!(buffer.as_str(), "1. a|2. b");
+}
+
+#[test]
+#[should_panic = "overflow a `u64`"]
+fn test_numbered_overflow() {
+    let mut buffer = Buffer::new::<64>();
+    let _ = buffer.write_numbered(&["a", "b"], u64::MAX, "");
+}
+
+#[test]
+#[should_panic = "overflow a `u64`"]
+fn test_numbered_aligned_overflow() {
+    let mut buffer = Buffer::new::<64>();
+    let _ = buffer.write_numbered_aligned(&["a", "b"], u64::MAX, "");
+}
+
+#[test]
+fn test_bool_bit() {
+    let mut buffer = Buffer::new::<2>();
+    buffer.write_bool_bit(true).unwrap();
+    buffer.write_bool_bit(false).unwrap();
+    assert_eq!(buffer.as_str(), "10");
+    assert!(buffer.write_bool_bit(true).is_err());
+}
+
+#[test]
+fn test_reverse() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.reverse();
+
+    buffer.push_str("12345").unwrap();
+    buffer.reverse();
+    assert_eq!(buffer.as_str(), "54321");
+
+    buffer.clear();
+    buffer.push_str("aé✓🦀b").unwrap();
+    buffer.reverse();
+    assert_eq!(buffer.as_str(), "b🦀✓éa");
+}
+
+#[test]
+fn test_chars() {
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("aé✓").unwrap();
+
+    assert!(buffer.chars().eq(['a', 'é', '✓']));
+    assert!(buffer.char_indices().eq([(0, 'a'), (1, 'é'), (3, '✓')]));
+    assert_eq!(buffer.char_count(), 3);
+}
+
+#[test]
+fn test_char_count() {
+    const COUNT: usize = {
+        let mut buffer = Buffer::new::<16>();
+        buffer.push_str_or_panic("🦀 crab");
+        buffer.char_count()
+    };
+
+    assert_eq!(COUNT, 6);
+
+    let mut buffer = Buffer::new::<32>();
+    assert_eq!(buffer.char_count(), 0);
+
+    for s in ["ascii", "héllo", "e\u{301}", "日本語", "a🦀b✓"] {
+        buffer.clear();
+        buffer.push_str(s).unwrap();
+        assert_eq!(buffer.char_count(), s.chars().count(), "{s:?}");
+    }
+}
+
+#[test]
+fn test_starts_ends_contains() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.push_str("key = value").unwrap();
+
+    assert!(buffer.starts_with(""));
+    assert!(buffer.starts_with("key"));
+    assert!(!buffer.starts_with("value"));
+    assert!(buffer.ends_with("value"));
+    assert!(!buffer.ends_with("key = value!"));
+    assert!(buffer.contains(" = "));
+    assert!(buffer.contains(""));
+    assert!(!buffer.contains("=="));
+
+    const {
+        let mut buffer = Buffer::new::<8>();
+        let _ = buffer.push_str("a=b");
+        assert!(buffer.contains("=") && buffer.starts_with("a") && buffer.ends_with("b"));
+    }
+}
+
+#[test]
+fn test_eq_str() {
+    const ANSWER: Buffer<[u8; 8]> = {
+        let mut buffer = Buffer::new::<8>();
+        let _ = buffer.write_u32(42);
+        buffer
+    };
+    const { assert!(ANSWER.eq_str("42")) };
+
+    assert!(!ANSWER.eq_str("4"));
+    assert!(!ANSWER.eq_str("420"));
+    assert!(Buffer::new::<0>().eq_str(""));
+}
+
+#[test]
+fn test_find() {
+    let mut buffer = Buffer::new::<16>();
+    assert_eq!(buffer.find(""), Some(0));
+    assert_eq!(buffer.find("a"), None);
+
+    buffer.push_str("é=1;b=2").unwrap();
+    assert_eq!(buffer.find("="), Some(2));
+    assert_eq!(buffer.find(";"), Some(4));
+    assert_eq!(buffer.find("b=2"), Some(5));
+    assert_eq!(buffer.find("b=22"), None);
+    assert_eq!(buffer.find(""), Some(0));
+
+    let (key, value) = buffer.as_str().split_at(buffer.find("=").unwrap());
+    assert_eq!((key, value), ("é", "=1;b=2"));
+}
+
+#[test]
+fn test_lines() {
+    let mut buffer = Buffer::new::<32>();
+    assert_eq!(buffer.line_count(), 0);
+    assert_eq!(buffer.nth_line(0), None);
+
+    for content in ["a", "a\n", "a\nbb\r\n\nccc", "\n\n", "x\r\ny\r", "é\n✓\n"] {
+        buffer.clear();
+        buffer.push_str(content).unwrap();
+
+        assert_eq!(buffer.line_count(), content.lines().count(), "{content:?}");
+
+        for n in 0..5 {
+            assert_eq!(
+                buffer.nth_line(n),
+                content.lines().nth(n),
+                "{content:?} {n}"
+            );
+        }
+    }
+
+    assert_eq!(buffer.nth_line(usize::MAX), None);
+}
+
+#[test]
+fn test_char_boundary() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.push_str("aé✓🦀").unwrap();
+    let s = buffer.as_str();
+
+    for idx in 0..=s.len() + 2 {
+        assert_eq!(
+            buffer.is_char_boundary(idx),
+            s.is_char_boundary(idx),
+            "{idx}"
+        );
+        assert_eq!(
+            buffer.floor_char_boundary(idx),
+            s.floor_char_boundary(idx),
+            "{idx}"
+        );
+    }
+
+    let empty = Buffer::new::<4>();
+    assert!(empty.is_char_boundary(0));
+    assert!(!empty.is_char_boundary(1));
+    assert_eq!(empty.floor_char_boundary(3), 0);
+}
+
+#[test]
+fn test_insert_char() {
+    let mut buffer = Buffer::new::<16>();
+    buffer.push_str("1234567").unwrap();
+    buffer.insert_char(4, ',').unwrap();
+    buffer.insert_char(1, ',').unwrap();
+    assert_eq!(buffer.as_str(), "1,234,567");
+
+    buffer.clear();
+    buffer.push_str("aé").unwrap();
+    buffer.insert_char(0, '✓').unwrap();
+    buffer.insert_char(4, '🦀').unwrap();
+    buffer.insert_char(buffer.len(), 'z').unwrap();
+    assert_eq!(buffer.as_str(), "✓a🦀éz");
+
+    let mut buffer = Buffer::new::<4>();
+    buffer.push_str("abc").unwrap();
+    assert!(buffer.insert_char(1, 'é').is_err());
+    assert_eq!(buffer.as_str(), "abc");
+    buffer.insert_char(1, '-').unwrap();
+    assert_eq!(buffer.as_str(), "a-bc");
+}
+
+#[test]
+#[should_panic = "not a char boundary"]
+fn test_insert_char_inside_char() {
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("é").unwrap();
+    let _ = buffer.insert_char(1, 'a');
+}
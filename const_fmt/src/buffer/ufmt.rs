@@ -0,0 +1,23 @@
+use super::{Buffer, BufferWriteFailed};
+use crate::ByteBuffer;
+
+impl<B: ByteBuffer> ::ufmt::uWrite for Buffer<B> {
+    type Error = BufferWriteFailed;
+
+    fn write_str(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        self.push_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> Result<(), BufferWriteFailed> {
+        Buffer::write_char(self, c)
+    }
+}
+
+#[test]
+fn test_uwrite() {
+    let mut buffer = Buffer::new::<16>();
+    ::ufmt::uwrite!(buffer, "{} + {}", 1u8, -2i32).unwrap();
+    assert_eq!(buffer.as_str(), "1 + -2");
+
+    assert!(::ufmt::uwrite!(buffer, "{}", "0123456789abc").is_err());
+}
@@ -0,0 +1,269 @@
+use core::time::Duration;
+
+use super::{Buffer, BufferWriteFailed, u64_digit_count};
+use crate::ByteBuffer;
+
+const BINARY_BYTE_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const DECIMAL_BYTE_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes a byte count using binary (1024-based) units, e.g. `512 B` or `1.50 MiB`.
+    ///
+    /// Anything from `1 KiB` up is written with two fractional digits, rounded half up.
+    pub const fn write_bytesize(&mut self, bytes: u64) -> Result<(), BufferWriteFailed> {
+        self.write_bytesize_imp(bytes, 1024, &BINARY_BYTE_UNITS)
+    }
+
+    /// Like [`write_bytesize`](Self::write_bytesize), but with decimal (1000-based) units,
+    /// e.g. `1.50 MB`.
+    pub const fn write_bytesize_decimal(&mut self, bytes: u64) -> Result<(), BufferWriteFailed> {
+        self.write_bytesize_imp(bytes, 1000, &DECIMAL_BYTE_UNITS)
+    }
+
+    /// Writes `d` as a clock time, `HH:MM:SS`, followed by `.mmm` if it has a
+    /// non-zero number of whole milliseconds.
+    ///
+    /// Every field is zero-padded to two digits, with the hours widening past `99`.
+    pub const fn write_duration_hms(&mut self, d: Duration) -> Result<(), BufferWriteFailed> {
+        let secs = d.as_secs();
+        let hours = secs / 3600;
+        let millis = d.subsec_millis();
+        let hours_len = u64_digit_count(hours);
+        let mut len = if hours_len > 2 { hours_len } else { 2 } + ":00:00".len();
+
+        if millis != 0 {
+            len += ".000".len();
+        }
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        tri!(self.write_u64_formatted(hours, 2, 0, ':'));
+        tri!(self.push_str(":"));
+        tri!(self.write_u64_formatted(secs / 60 % 60, 2, 0, ':'));
+        tri!(self.push_str(":"));
+        tri!(self.write_u64_formatted(secs % 60, 2, 0, ':'));
+
+        if millis != 0 {
+            tri!(self.push_str("."));
+            tri!(self.write_u64_formatted(millis as u64, 3, 0, ':'));
+        }
+
+        Ok(())
+    }
+
+    /// Writes a Unix timestamp as a UTC date and time, `YYYY-MM-DDTHH:MM:SS`.
+    ///
+    /// Years past 9999 are written with however many digits they need.
+    pub const fn write_timestamp(&mut self, epoch_secs: u64) -> Result<(), BufferWriteFailed> {
+        let (year, month, day) = civil_from_days(epoch_secs / 86400);
+        let secs = epoch_secs % 86400;
+        let year_len = u64_digit_count(year);
+        let len = if year_len > 4 { year_len } else { 4 } + "-MM-DDTHH:MM:SS".len();
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        tri!(self.write_u64_formatted(year, 4, 0, '-'));
+        tri!(self.push_str("-"));
+        tri!(self.write_u64_formatted(month, 2, 0, '-'));
+        tri!(self.push_str("-"));
+        tri!(self.write_u64_formatted(day, 2, 0, '-'));
+        tri!(self.push_str("T"));
+        tri!(self.write_u64_formatted(secs / 3600, 2, 0, ':'));
+        tri!(self.push_str(":"));
+        tri!(self.write_u64_formatted(secs / 60 % 60, 2, 0, ':'));
+        tri!(self.push_str(":"));
+        tri!(self.write_u64_formatted(secs % 60, 2, 0, ':'));
+
+        Ok(())
+    }
+
+    const fn write_bytesize_imp(
+        &mut self,
+        bytes: u64,
+        base: u64,
+        units: &[&str; 7],
+    ) -> Result<(), BufferWriteFailed> {
+        let mut unit = 0;
+        let mut scale = 1;
+
+        while unit + 1 < units.len() && bytes / scale >= base {
+            scale *= base;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            let len = u64_digit_count(bytes) + 1 + units[0].len();
+
+            if len > self.remaining_capacity() {
+                return Err(BufferWriteFailed);
+            }
+
+            tri!(self.write_u64(bytes));
+            tri!(self.push_str(" "));
+            return self.push_str(units[0]);
+        }
+
+        let mut hundredths = scaled_hundredths(bytes, scale);
+
+        // rounding may carry into the next unit (e.g. 1023.996 KiB)
+        if hundredths >= base * 100 && unit + 1 < units.len() {
+            scale *= base;
+            unit += 1;
+            hundredths = scaled_hundredths(bytes, scale);
+        }
+
+        let int = hundredths / 100;
+        let frac = (hundredths % 100) as u8;
+        let len = u64_digit_count(int) + ".00 ".len() + units[unit].len();
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        tri!(self.write_u64(int));
+        tri!(self.push_str("."));
+        tri!(self.write_u8(frac / 10));
+        tri!(self.write_u8(frac % 10));
+        tri!(self.push_str(" "));
+        self.push_str(units[unit])
+    }
+}
+
+const fn scaled_hundredths(bytes: u64, scale: u64) -> u64 {
+    let scale = scale as u128;
+    ((bytes as u128 * 100 + scale / 2) / scale) as u64
+}
+
+// the year, month and day of the `days`th day after 1970-01-01, using Howard Hinnant's
+// `civil_from_days`, where years start in March so the leap day is the last day of the year
+const fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days % 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + (month <= 2) as u64;
+
+    (year, month, day)
+}
+
+#[test]
+fn test_bytesize() {
+    let cases: &[(u64, &str)] = &[
+        (0, "0 B"),
+        (512, "512 B"),
+        (1023, "1023 B"),
+        (1024, "1.00 KiB"),
+        (1536, "1.50 KiB"),
+        (1024 * 1024 - 1, "1.00 MiB"),
+        (3 * 1024 * 1024 / 2, "1.50 MiB"),
+        (5 * 1024 * 1024 * 1024, "5.00 GiB"),
+        (u64::MAX, "16.00 EiB"),
+    ];
+
+    for &(bytes, expected) in cases {
+        let mut buffer = Buffer::new::<16>();
+        buffer.write_bytesize(bytes).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+}
+
+#[test]
+fn test_bytesize_decimal() {
+    let cases: &[(u64, &str)] = &[
+        (0, "0 B"),
+        (999, "999 B"),
+        (1000, "1.00 kB"),
+        (1024, "1.02 kB"),
+        (999_999, "1.00 MB"),
+        (u64::MAX, "18.45 EB"),
+    ];
+
+    for &(bytes, expected) in cases {
+        let mut buffer = Buffer::new::<16>();
+        buffer.write_bytesize_decimal(bytes).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+}
+
+#[test]
+fn test_bytesize_capacity() {
+    let mut buffer = Buffer::new::<7>();
+    assert!(buffer.write_bytesize(1536).is_err());
+    assert!(buffer.is_empty());
+
+    let mut buffer = Buffer::new::<8>();
+    buffer.write_bytesize(1536).unwrap();
+    assert_eq!(buffer.as_str(), "1.50 KiB");
+}
+
+#[test]
+fn test_duration_hms() {
+    let cases = [
+        (Duration::ZERO, "00:00:00"),
+        (Duration::from_secs(59), "00:00:59"),
+        (Duration::from_secs(3661), "01:01:01"),
+        (Duration::from_millis(45_296_789), "12:34:56.789"),
+        (Duration::from_millis(5), "00:00:00.005"),
+        (Duration::from_micros(999), "00:00:00"),
+        (Duration::from_secs(100 * 3600 + 59 * 60), "100:59:00"),
+        (Duration::MAX, "5124095576030431:00:15.999"),
+    ];
+
+    for (d, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.write_duration_hms(d).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    let mut buffer = Buffer::new::<11>();
+    assert!(buffer.write_duration_hms(Duration::from_millis(1)).is_err());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_timestamp() {
+    let cases = [
+        (0, "1970-01-01T00:00:00"),
+        (951782400, "2000-02-29T00:00:00"),
+        (1709251199, "2024-02-29T23:59:59"),
+        (4107542400, "2100-03-01T00:00:00"),
+        (253402300799, "9999-12-31T23:59:59"),
+        (253402300800, "10000-01-01T00:00:00"),
+        (u64::MAX, "584554051223-11-09T07:00:15"),
+    ];
+
+    for (secs, expected) in cases {
+        let mut buffer = Buffer::new::<32>();
+        buffer.write_timestamp(secs).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+
+    for secs in crate::test_rng::random_u64s(0x7157).take(10_000) {
+        let secs = secs % 253402300800;
+        let date = chrono::DateTime::from_timestamp(secs as i64, 0).unwrap();
+
+        let mut buffer = Buffer::new::<32>();
+        buffer.write_timestamp(secs).unwrap();
+        assert_eq!(
+            buffer.as_str(),
+            date.format("%Y-%m-%dT%H:%M:%S").to_string()
+        );
+    }
+
+    let mut buffer = Buffer::new::<18>();
+    assert!(buffer.write_timestamp(0).is_err());
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,69 @@
+use super::hex::{HEX_LOWER, HEX_UPPER};
+use super::{Buffer, BufferWriteFailed};
+use crate::ByteBuffer;
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Writes a UUID in the canonical lowercase `8-4-4-4-12` form, e.g.
+    /// `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    pub const fn write_uuid(&mut self, bytes: [u8; 16]) -> Result<(), BufferWriteFailed> {
+        self.write_uuid_imp(bytes, true, HEX_LOWER)
+    }
+
+    /// Like [`write_uuid`](Self::write_uuid), but with uppercase hex digits.
+    pub const fn write_uuid_upper(&mut self, bytes: [u8; 16]) -> Result<(), BufferWriteFailed> {
+        self.write_uuid_imp(bytes, true, HEX_UPPER)
+    }
+
+    /// Writes a UUID as 32 lowercase hex digits without dashes.
+    pub const fn write_uuid_simple(&mut self, bytes: [u8; 16]) -> Result<(), BufferWriteFailed> {
+        self.write_uuid_imp(bytes, false, HEX_LOWER)
+    }
+
+    const fn write_uuid_imp(
+        &mut self,
+        bytes: [u8; 16],
+        dashes: bool,
+        alphabet: &[u8; 16],
+    ) -> Result<(), BufferWriteFailed> {
+        let len = if dashes { 36 } else { 32 };
+
+        if len > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if dashes && matches!(i, 4 | 6 | 8 | 10) {
+                unsafe { self.push_byte_unchecked(b'-') };
+            }
+
+            unsafe { self.push_hex_unchecked(bytes[i] as u64, 2, alphabet) };
+            i += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_uuid() {
+    let uuid = [
+        0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0,
+        0xc8,
+    ];
+
+    let mut buffer = Buffer::new::<36>();
+    buffer.write_uuid(uuid).unwrap();
+    assert_eq!(buffer.as_str(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert!(buffer.write_uuid_simple(uuid).is_err());
+
+    let mut buffer = Buffer::new::<36>();
+    buffer.write_uuid_upper(uuid).unwrap();
+    assert_eq!(buffer.as_str(), "67E55044-10B1-426F-9247-BB680E5FE0C8");
+
+    let mut buffer = Buffer::new::<35>();
+    assert!(buffer.write_uuid([0; 16]).is_err());
+    buffer.write_uuid_simple([0; 16]).unwrap();
+    assert_eq!(buffer.as_str(), "00000000000000000000000000000000");
+}
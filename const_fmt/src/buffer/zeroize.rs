@@ -0,0 +1,84 @@
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{Ordering, compiler_fence};
+
+use super::Buffer;
+use crate::ByteBuffer;
+
+impl<B: ByteBuffer> Buffer<B> {
+    /// Overwrites the written content with zeros, then clears the buffer.
+    ///
+    /// The writes are volatile, so they aren't optimized away even if the buffer is never
+    /// read again.
+    pub fn clear_zeroize(&mut self) {
+        let ptr = self.as_mut_ptr();
+
+        for i in 0..self.len() {
+            unsafe { ptr.add(i).write_volatile(0) };
+        }
+
+        compiler_fence(Ordering::SeqCst);
+        self.clear();
+    }
+}
+
+/// A [`Buffer`] that zeros its written content when dropped, for formatting secrets.
+///
+/// Calling [`Buffer::clear`] through this wrapper doesn't zero anything; use
+/// [`Buffer::clear_zeroize`] instead.
+pub struct Zeroizing<B: ByteBuffer>(Buffer<B>);
+
+impl<B: ByteBuffer> Zeroizing<B> {
+    pub const fn new(buffer: Buffer<B>) -> Self {
+        Self(buffer)
+    }
+}
+
+impl<B: ByteBuffer> Deref for Zeroizing<B> {
+    type Target = Buffer<B>;
+
+    fn deref(&self) -> &Buffer<B> {
+        &self.0
+    }
+}
+
+impl<B: ByteBuffer> DerefMut for Zeroizing<B> {
+    fn deref_mut(&mut self) -> &mut Buffer<B> {
+        &mut self.0
+    }
+}
+
+impl<B: ByteBuffer> Drop for Zeroizing<B> {
+    fn drop(&mut self) {
+        self.0.clear_zeroize();
+    }
+}
+
+#[test]
+fn test_clear_zeroize() {
+    let mut buffer = Buffer::new::<8>();
+    buffer.push_str("secret").unwrap();
+    buffer.clear_zeroize();
+    assert!(buffer.is_empty());
+
+    // the old bytes are gone, not just hidden behind `len`
+    let bytes = unsafe { core::slice::from_raw_parts(buffer.as_ptr(), 6) };
+    assert_eq!(bytes, [0; 6]);
+}
+
+#[test]
+fn test_zeroizing() {
+    let mut slot = core::mem::MaybeUninit::new(Zeroizing::new(Buffer::new::<16>()));
+    let token = slot.as_mut_ptr();
+
+    unsafe {
+        (*token).push_str("Bearer ").unwrap();
+        (*token).write_u64(1234).unwrap();
+        assert_eq!((*token).as_str(), "Bearer 1234");
+
+        token.drop_in_place();
+
+        // the value is gone, but its storage in `slot` is still there to inspect
+        let bytes = (&raw const (*token).0.buffer).cast::<[u8; 11]>().read();
+        assert_eq!(bytes, [0; 11]);
+    }
+}
@@ -1,15 +1,103 @@
-pub trait Seal {}
+/// Storage that a [`Buffer`](crate::Buffer) writes its content into.
+///
+/// This is implemented for `[u8; N]`, tuples of up to four byte arrays, [`Concat`] and
+/// [`Concat3`], and can be implemented for custom backings such as an over-aligned
+/// wrapper around a byte array. A `Buffer<Self>` can then be made with
+/// [`Buffer::create`](crate::Buffer::create).
+///
+/// # Safety
+///
+/// A `Buffer<Self>` treats `Self` as `size_of::<Self>()` bytes of raw storage, and that is
+/// exactly its capacity. It never reads, creates, or drops a `Self` value. Implementors
+/// must ensure that:
+///
+/// * `Self` has no padding bytes, since padding may be lost when a buffer is moved
+/// * every byte of `Self` may be overwritten with arbitrary data
+pub unsafe trait ByteBuffer {}
 
-pub unsafe trait ByteBuffer: Seal {}
-
-impl<const N: usize> Seal for [u8; N] {}
 unsafe impl<const N: usize> ByteBuffer for [u8; N] {}
 
-#[repr(C)]
+// tuples don't have a guaranteed layout, so this is only done for byte arrays: every field
+// has an alignment of 1, so there is never a reason to put padding between or after them.
+// Tuples of arbitrary `ByteBuffer`s may contain over-aligned fields, and so may be padded.
+macro_rules! byte_array_tuple {
+    ($($n:ident)*) => {
+        unsafe impl<$(const $n: usize),*> ByteBuffer for ($([u8; $n],)*) {}
+    };
+}
+
+byte_array_tuple! { A B }
+byte_array_tuple! { A B C }
+byte_array_tuple! { A B C D }
+
+// packed so that no padding can end up between the two halves,
+// even if one of them is over-aligned
+#[repr(C, packed)]
 pub struct Concat<A, B> {
     a: A,
     b: B,
 }
 
-impl<A: Seal, B: Seal> Seal for Concat<A, B> {}
 unsafe impl<A: ByteBuffer, B: ByteBuffer> ByteBuffer for Concat<A, B> {}
+
+impl<A: Copy, B: Copy> Clone for Concat<A, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: Copy, B: Copy> Copy for Concat<A, B> {}
+
+// like `Concat`, but with three parts, so three-way joins don't need nested types
+#[repr(C, packed)]
+pub struct Concat3<A, B, C> {
+    a: A,
+    b: B,
+    c: C,
+}
+
+unsafe impl<A: ByteBuffer, B: ByteBuffer, C: ByteBuffer> ByteBuffer for Concat3<A, B, C> {}
+
+impl<A: Copy, B: Copy, C: Copy> Clone for Concat3<A, B, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: Copy, B: Copy, C: Copy> Copy for Concat3<A, B, C> {}
+
+#[test]
+fn test_custom_backing() {
+    use crate::Buffer;
+
+    #[repr(C, align(64))]
+    struct Aligned([u8; 128]);
+
+    unsafe impl ByteBuffer for Aligned {}
+
+    let mut buffer = Buffer::<Aligned>::create();
+    assert_eq!(buffer.capacity(), 128);
+    buffer.write_u32(1234).unwrap();
+    assert_eq!(buffer.as_str(), "1234");
+    assert_eq!(buffer.as_str().as_ptr() as usize % 64, 0);
+
+    let mut small = Buffer::new::<3>();
+    small.push_str("ab").unwrap();
+    let joined = small.append(&buffer);
+    assert_eq!(joined.capacity(), 131);
+    assert_eq!(joined.as_str(), "ab1234");
+}
+
+#[test]
+fn test_tuple_backing() {
+    use crate::Buffer;
+
+    let mut buffer = Buffer::<([u8; 4], [u8; 4])>::create();
+    assert_eq!(buffer.capacity(), 8);
+    buffer.push_str("12345678").unwrap();
+    assert!(buffer.push_str("9").is_err());
+    assert_eq!(buffer.as_str(), "12345678");
+
+    let buffer = Buffer::<([u8; 1], [u8; 0], [u8; 7], [u8; 16])>::create();
+    assert_eq!(buffer.capacity(), 24);
+}
@@ -0,0 +1,119 @@
+use crate::{Buffer, BufferWriteFailed, ByteBuffer};
+
+/// A [`Buffer`] that remembers whether any write failed, so a long sequence of writes can
+/// be checked once with [`finish`](Self::finish).
+///
+/// Each write returns `&mut Self` for chaining. After the first failure, every later write
+/// is skipped, so the content is never missing a piece from the middle.
+pub struct CheckedBuffer<B: ByteBuffer> {
+    buffer: Buffer<B>,
+    failed: bool,
+}
+
+macro_rules! checked {
+    ($($arg:ty => $writefun:ident)*) => {$(
+        #[doc = concat!(
+            "Calls [`Buffer::", stringify!($writefun), "`] unless a write already failed.",
+        )]
+        pub const fn $writefun(&mut self, value: $arg) -> &mut Self {
+            if !self.failed && self.buffer.$writefun(value).is_err() {
+                self.failed = true;
+            }
+
+            self
+        }
+    )*};
+}
+
+impl CheckedBuffer<[u8; 0]> {
+    pub const fn new<const N: usize>() -> CheckedBuffer<[u8; N]> {
+        CheckedBuffer::create()
+    }
+}
+
+impl<B: ByteBuffer> CheckedBuffer<B> {
+    pub const fn create() -> Self {
+        Self {
+            buffer: Buffer::create(),
+            failed: false,
+        }
+    }
+
+    /// Returns whether any write has failed so far.
+    pub const fn has_failed(&self) -> bool {
+        self.failed
+    }
+
+    /// The content written so far, up to the first failed write.
+    pub const fn as_str(&self) -> &str {
+        self.buffer.as_str()
+    }
+
+    /// Returns the buffer if every write succeeded.
+    pub const fn finish(self) -> Result<Buffer<B>, BufferWriteFailed> {
+        if self.failed {
+            Err(BufferWriteFailed)
+        } else {
+            Ok(self.buffer)
+        }
+    }
+
+    /// Runs `write` on the buffer unless a write already failed, for writers without a
+    /// method here.
+    pub fn write(
+        &mut self,
+        write: impl FnOnce(&mut Buffer<B>) -> Result<(), BufferWriteFailed>,
+    ) -> &mut Self {
+        if !self.failed && write(&mut self.buffer).is_err() {
+            self.failed = true;
+        }
+
+        self
+    }
+
+    checked! {
+        &str => push_str
+        char => write_char
+        bool => write_bool_bit
+        u8 => write_u8
+        u16 => write_u16
+        u32 => write_u32
+        u64 => write_u64
+        u128 => write_u128
+        usize => write_usize
+        i8 => write_i8
+        i16 => write_i16
+        i32 => write_i32
+        i64 => write_i64
+        i128 => write_i128
+        isize => write_isize
+    }
+}
+
+#[test]
+fn test_checked_buffer() {
+    const POINT: Result<Buffer<[u8; 16]>, BufferWriteFailed> = {
+        let mut buffer = CheckedBuffer::new::<16>();
+        buffer
+            .push_str("(")
+            .write_i32(-3)
+            .push_str(", ")
+            .write_u8(7)
+            .write_char(')');
+        buffer.finish()
+    };
+
+    assert_eq!(POINT.unwrap().as_str(), "(-3, 7)");
+
+    let mut buffer = CheckedBuffer::new::<6>();
+    buffer.push_str("id=").write_u32(12_345).push_str("!");
+    buffer.write(|buffer| buffer.write_hex_color([0, 0, 0]));
+    assert!(buffer.has_failed());
+    // nothing after the failed write made it in, even though `!` would have fit
+    assert_eq!(buffer.as_str(), "id=");
+    assert!(buffer.finish().is_err());
+
+    let mut buffer = CheckedBuffer::new::<4>();
+    buffer.write(|buffer| buffer.write_f64(1.5, 1));
+    assert_eq!(buffer.finish().unwrap().as_str(), "1.5");
+}
@@ -1,11 +1,42 @@
+//! # Features
+//!
+//! None of these are enabled by default.
+//!
+//! * `alloc`: conversions into `alloc::string::String`
+//! * `derive`: `#[derive(ConstFormat)]`
+//! * `defmt`: implements `defmt::Format` (defmt 1.x) for `Buffer`
+//! * `heapless`: conversions into `heapless::String` (heapless 0.9)
+//! * `ufmt`: implements `ufmt::uWrite` (ufmt 0.2) for `Buffer`
+
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[macro_use]
 #[doc(hidden)]
 pub mod macros;
 
 mod buffer;
 mod byte_buffer;
+mod checked_buffer;
+mod slice_buffer;
+mod stream_writer;
+#[cfg(test)]
+mod test_rng;
+mod utf8;
+
+pub use buffer::{
+    Align, Buffer, BufferWriteFailed, ByteOrder, FormatSpec, HEXDUMP_BYTES_PER_LINE, ParseError,
+    RoundingMode, Sign, WidthError, Zeroizing, f64_fraction_digits, f64_integer_part, join,
+    u8_digit_count, u16_digit_count, u32_digit_count, u64_digit_count, u64_digit_count_radix,
+    u128_digit_count, usize_digit_count,
+};
+pub use byte_buffer::{ByteBuffer, Concat, Concat3};
+pub use checked_buffer::CheckedBuffer;
+pub use slice_buffer::SliceBuffer;
+pub use stream_writer::StreamWriter;
+pub use utf8::{from_utf8, is_valid_utf8};
 
-pub use buffer::{Buffer, BufferWriteFailed};
-pub use byte_buffer::{ByteBuffer, Concat};
+#[cfg(feature = "derive")]
+pub use const_fmt_derive::ConstFormat;
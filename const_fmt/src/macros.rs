@@ -20,8 +20,28 @@ pub trait Writer {
     const INIT: Self;
 }
 
+/// A type that the formatting macros know how to write.
+///
+/// `#[derive(ConstFormat)]` implements every item. A hand written impl must provide
+/// [`write_to`](Self::write_to) as well as the `Writer`: it's a required method, so impls
+/// written before it was added no longer compile. [`max_len`](Self::max_len) has a
+/// default.
 pub trait ConstFormat {
     type Writer: Writer;
+
+    /// Writes `self` the same way its [`Writer`](Self::Writer) does, for generic code.
+    ///
+    /// The writers' `display` methods aren't part of any trait, so there is no default
+    /// that could forward to them.
+    fn write_to<B: ByteBuffer>(&self, buffer: &mut Buffer<B>) -> Result<(), BufferWriteFailed>;
+
+    /// The most bytes `self` can take up when written, which is never less than what
+    /// [`write_to`](Self::write_to) writes.
+    ///
+    /// The default of `usize::MAX` is always a valid bound, if not a useful one.
+    fn max_len(&self) -> usize {
+        usize::MAX
+    }
 }
 
 pub trait Selection {
@@ -48,28 +68,222 @@ impl<T> Writer for ConstFormatNotImplemented<T> {
     const INIT: Self = Self(PhantomData);
 }
 
+// the writer used by `#[derive(ConstFormat)]`, which forwards to `DerivedDisplay`
+pub struct DerivedWriter<T>(PhantomData<T>);
+
+impl<T> Writer for DerivedWriter<T> {
+    const INIT: Self = Self(PhantomData);
+}
+
+pub trait DerivedDisplay {
+    fn display<B: ByteBuffer>(&self, buffer: &mut Buffer<B>) -> Result<(), BufferWriteFailed>;
+
+    fn max_len(&self) -> usize;
+}
+
+impl<T: DerivedDisplay> DerivedWriter<T> {
+    pub fn display<B: ByteBuffer>(
+        self,
+        value: &T,
+        buffer: &mut Buffer<B>,
+    ) -> Result<(), BufferWriteFailed> {
+        value.display(buffer)
+    }
+
+    pub fn max_len(self, value: &T) -> usize {
+        value.max_len()
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! get_writer {
-    ($val:ident) => {{
+    ($val:expr) => {{
         use $crate::macros::Selection;
 
-        get_writer(|inf| (&&&&$crate::macros::Selector(&$val)).select(inf))
+        $crate::macros::get_writer(|inf| (&&&&$crate::macros::Selector(&$val)).select(inf))
     }};
 }
 
+// references format like the value they point to
+impl<T: ConstFormat> ConstFormat for &T {
+    type Writer = T::Writer;
+
+    fn write_to<B: ByteBuffer>(&self, buffer: &mut Buffer<B>) -> Result<(), BufferWriteFailed> {
+        (**self).write_to(buffer)
+    }
+
+    fn max_len(&self) -> usize {
+        (**self).max_len()
+    }
+}
+
+impl<T: ConstFormat> ConstFormat for &mut T {
+    type Writer = T::Writer;
+
+    fn write_to<B: ByteBuffer>(&self, buffer: &mut Buffer<B>) -> Result<(), BufferWriteFailed> {
+        (**self).write_to(buffer)
+    }
+
+    fn max_len(&self) -> usize {
+        (**self).max_len()
+    }
+}
+
+/// The most bytes any value can take up when written by this [`Writer`], for the
+/// writers of fixed size types. Their per-value `max_len` and
+/// [`const_assert_fits!`](crate::const_assert_fits) both use it.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't have a maximum formatted length for every value"
+)]
+pub trait MaxLen: Writer {
+    const MAX_LEN: usize;
+}
+
+// the writer bound of a type, as used by `const_assert_fits!`
+pub const fn type_max_len<T: ConstFormat<Writer: MaxLen> + ?Sized>() -> usize {
+    <T::Writer as MaxLen>::MAX_LEN
+}
+
+// the worst case length of `fmt` with each `{}` replaced by the matching length in `arg_lens`
+pub const fn max_formatted_len(fmt: &str, arg_lens: &[usize]) -> usize {
+    let bytes = fmt.as_bytes();
+    let mut len = 0;
+    let mut args = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let next = if i + 1 < bytes.len() { bytes[i + 1] } else { 0 };
+
+        match (bytes[i], next) {
+            (b'{', b'{') | (b'}', b'}') => {
+                len += 1;
+                i += 2;
+            }
+            (b'{', b'}') => {
+                assert!(
+                    args < arg_lens.len(),
+                    "more placeholders than argument types"
+                );
+                len += arg_lens[args];
+                args += 1;
+                i += 2;
+            }
+            (b'{' | b'}', _) => panic!("only empty placeholders and escaped braces are supported"),
+            _ => {
+                len += 1;
+                i += 1;
+            }
+        }
+    }
+
+    assert!(
+        args == arg_lens.len(),
+        "fewer placeholders than argument types"
+    );
+    len
+}
+
+/// Fails to compile unless a buffer of capacity `N` can hold `fmt` formatted with
+/// arguments of the given types, in their worst case.
+///
+/// Only `{}` placeholders and the `{{`/`}}` escapes are understood, and every argument
+/// type must implement [`ConstFormat`](crate::macros::ConstFormat) with a writer that
+/// implements [`MaxLen`](crate::macros::MaxLen), like the integer types and `char` do.
+///
+/// ```
+/// const_fmt::const_assert_fits!(19, "x = {}, y = {}", u8, i16);
+/// ```
+///
+/// ```compile_fail
+/// const_fmt::const_assert_fits!(8, "x = {}", u32);
+/// ```
+#[macro_export]
+macro_rules! const_assert_fits {
+    ($capacity:expr, $fmt:literal $(, $ty:ty)* $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::macros::max_formatted_len(
+                $fmt,
+                &[$($crate::macros::type_max_len::<$ty>()),*],
+            ) <= $capacity,
+            "the buffer is too small for the worst case output",
+        );
+    };
+}
+
+/// Fails to compile unless the buffer `$buffer`, built in `const`, holds exactly
+/// `$expected`. The error shows both strings.
+///
+/// ```
+/// use const_fmt::Buffer;
+///
+/// const ANSWER: Buffer<[u8; 8]> = {
+///     let mut buffer = Buffer::new::<8>();
+///     buffer.write_u32_or_panic(42);
+///     buffer
+/// };
+///
+/// const_fmt::const_assert_fmt_eq!(ANSWER, "42");
+/// ```
+///
+/// ```compile_fail
+/// # use const_fmt::Buffer;
+/// const ANSWER: Buffer<[u8; 8]> = Buffer::new::<8>();
+/// const_fmt::const_assert_fmt_eq!(ANSWER, "42");
+/// ```
+#[macro_export]
+macro_rules! const_assert_fmt_eq {
+    ($buffer:expr, $expected:expr $(,)?) => {
+        const _: () = {
+            let buffer = &$buffer;
+            let expected: &str = $expected;
+
+            if !buffer.eq_str(expected) {
+                $crate::macros::fmt_eq_failed(buffer.as_str(), expected);
+            }
+        };
+    };
+}
+
+#[track_caller]
+pub const fn fmt_eq_failed(got: &str, expected: &str) -> ! {
+    // long strings are cut off, which is fine for an error message
+    let mut message = Buffer::new::<1024>();
+    let _ = message.try_push_str("the buffer holds `");
+    let _ = message.try_push_str(got);
+    let _ = message.try_push_str("`, but `");
+    let _ = message.try_push_str(expected);
+    let _ = message.try_push_str("` was expected");
+    panic!("{}", message.as_str())
+}
+
 pub struct StdWriter<T>(PhantomData<T>);
 
 macro_rules! int {
-    ($int:ident $func:ident) => {
+    ($int:ident $func:ident $max_len:expr) => {
         impl ConstFormat for $int {
             type Writer = StdWriter<Self>;
+
+            fn write_to<B: ByteBuffer>(
+                &self,
+                buffer: &mut Buffer<B>,
+            ) -> Result<(), BufferWriteFailed> {
+                buffer.$func(*self)
+            }
+
+            fn max_len(&self) -> usize {
+                <Self::Writer as MaxLen>::MAX_LEN
+            }
         }
 
         impl Writer for StdWriter<$int> {
             const INIT: Self = Self(PhantomData);
         }
 
+        impl MaxLen for StdWriter<$int> {
+            const MAX_LEN: usize = $max_len;
+        }
+
         impl StdWriter<$int> {
             pub fn display<B: ByteBuffer>(
                 self,
@@ -78,28 +292,42 @@ macro_rules! int {
             ) -> Result<(), BufferWriteFailed> {
                 buffer.$func(*value)
             }
+
+            // every value of a fixed size type has the same bound
+            pub const fn max_len(self, _value: &$int) -> usize {
+                <Self as MaxLen>::MAX_LEN
+            }
         }
     };
 }
 
-int!(u8 write_u8);
-int!(u16 write_u16);
-int!(u32 write_u32);
-int!(u64 write_u64);
-int!(u128 write_u128);
-int!(usize write_usize);
+int!(u8 write_u8 crate::u8_digit_count(u8::MAX));
+int!(u16 write_u16 crate::u16_digit_count(u16::MAX));
+int!(u32 write_u32 crate::u32_digit_count(u32::MAX));
+int!(u64 write_u64 crate::u64_digit_count(u64::MAX));
+int!(u128 write_u128 crate::u128_digit_count(u128::MAX));
+int!(usize write_usize crate::usize_digit_count(usize::MAX));
 
-int!(i8 write_i8);
-int!(i16 write_i16);
-int!(i32 write_i32);
-int!(i64 write_i64);
-int!(i128 write_i128);
-int!(isize write_isize);
+// the minimum has the most digits, plus the sign
+int!(i8 write_i8 1 + crate::u8_digit_count(i8::MIN.unsigned_abs()));
+int!(i16 write_i16 1 + crate::u16_digit_count(i16::MIN.unsigned_abs()));
+int!(i32 write_i32 1 + crate::u32_digit_count(i32::MIN.unsigned_abs()));
+int!(i64 write_i64 1 + crate::u64_digit_count(i64::MIN.unsigned_abs()));
+int!(i128 write_i128 1 + crate::u128_digit_count(i128::MIN.unsigned_abs()));
+int!(isize write_isize 1 + crate::usize_digit_count(isize::MIN.unsigned_abs()));
 
-int!(char write_char);
+int!(char write_char char::MAX.len_utf8());
 
 impl ConstFormat for &str {
     type Writer = StdWriter<Self>;
+
+    fn write_to<B: ByteBuffer>(&self, buffer: &mut Buffer<B>) -> Result<(), BufferWriteFailed> {
+        buffer.push_str(self)
+    }
+
+    fn max_len(&self) -> usize {
+        self.len()
+    }
 }
 
 impl Writer for StdWriter<&str> {
@@ -114,6 +342,10 @@ impl StdWriter<&str> {
     ) -> Result<(), BufferWriteFailed> {
         buffer.push_str(value)
     }
+
+    pub const fn max_len(self, value: &str) -> usize {
+        value.len()
+    }
 }
 
 #[test]
@@ -125,3 +357,109 @@ fn test() {
 
     assert_eq!(buffer.as_str(), "0");
 }
+
+#[test]
+fn test_references() {
+    let x = 42u8;
+    let r = &x;
+    let rr = &r;
+    let s = "hi";
+    let rs = &s;
+    let mut y = -7i32;
+    let m = &mut y;
+
+    let mut buffer = Buffer::new::<20>();
+    get_writer!(r).display(r, &mut buffer).unwrap();
+    get_writer!(rr).display(rr, &mut buffer).unwrap();
+    get_writer!(rs).display(rs, &mut buffer).unwrap();
+    get_writer!(m).display(m, &mut buffer).unwrap();
+    assert_eq!(buffer.as_str(), "4242hi-7");
+
+    // types without `ConstFormat` still fall back to the not-implemented writer
+    struct NoFormat;
+    let value = &NoFormat;
+    let _: ConstFormatNotImplemented<&NoFormat> = get_writer!(value);
+}
+
+#[test]
+fn test_max_len() {
+    const U64_LEN: usize = get_writer!(0u64).max_len(&0);
+    const STR_LEN: usize = get_writer!("abc").max_len("abc");
+    assert_eq!(U64_LEN, 20);
+    assert_eq!(STR_LEN, 3);
+
+    let x = -5i8;
+    let r = &x;
+    assert_eq!(get_writer!(r).max_len(r), 4);
+    assert_eq!(get_writer!('é').max_len(&'é'), 4);
+
+    // the generic bound is never smaller than what actually gets written
+    for value in [0, 1, u64::MAX] {
+        let mut buffer = Buffer::new::<20>();
+        value.write_to(&mut buffer).unwrap();
+        assert!(buffer.len() <= value.max_len());
+    }
+
+    assert_eq!(ConstFormat::max_len(&"hello"), 5);
+
+    // hand written impls don't have to give a bound
+    struct Custom;
+
+    impl ConstFormat for Custom {
+        type Writer = ConstFormatNotImplemented<Self>;
+
+        fn write_to<B: ByteBuffer>(&self, buffer: &mut Buffer<B>) -> Result<(), BufferWriteFailed> {
+            buffer.push_str("custom")
+        }
+    }
+
+    assert_eq!(Custom.max_len(), usize::MAX);
+}
+
+#[test]
+fn test_const_assert_fmt_eq() {
+    const POINT: Buffer<[u8; 16]> = {
+        let mut buffer = Buffer::new::<16>();
+        buffer.push_str_or_panic("(1, ");
+        buffer.write_i8_or_panic(-2);
+        buffer.push_str_or_panic(")");
+        buffer
+    };
+
+    const_assert_fmt_eq!(POINT, "(1, -2)");
+    const_assert_fmt_eq!(Buffer::new::<0>(), "");
+}
+
+#[test]
+#[should_panic = "the buffer holds `ab`, but `abc` was expected"]
+fn test_fmt_eq_failed() {
+    fmt_eq_failed("ab", "abc");
+}
+
+#[test]
+fn test_max_formatted_len() {
+    assert_eq!(max_formatted_len("", &[]), 0);
+    assert_eq!(max_formatted_len("x = {}", &[type_max_len::<u32>()]), 14);
+    assert_eq!(max_formatted_len("{{{}}}", &[type_max_len::<i8>()]), 6);
+    assert_eq!(
+        max_formatted_len("{}{}", &[type_max_len::<i128>(), type_max_len::<&char>()]),
+        44
+    );
+    assert_eq!(type_max_len::<usize>(), usize::MAX.to_string().len());
+    assert_eq!(type_max_len::<isize>(), isize::MIN.to_string().len());
+
+    const_assert_fits!(14, "x = {}", u32);
+    const_assert_fits!(27, "{} < {}", i64, char,);
+}
+
+#[test]
+#[should_panic = "more placeholders than argument types"]
+fn test_max_formatted_len_missing_arg() {
+    max_formatted_len("{} {}", &[3]);
+}
+
+#[test]
+#[should_panic = "only empty placeholders"]
+fn test_max_formatted_len_named() {
+    max_formatted_len("{x}", &[3]);
+}
@@ -0,0 +1,158 @@
+use crate::{Buffer, BufferWriteFailed};
+
+/// A buffer that writes into borrowed storage instead of owning a `[u8; N]`.
+///
+/// This is useful for scratch space that is too large, or only known at runtime.
+/// Any bytes already in the slice are ignored and overwritten.
+///
+/// Only the basic writers ([`push_str`](Self::push_str), [`write_char`](Self::write_char)
+/// and the integer writers) are available directly, as `const fn`s. The rest of
+/// [`Buffer`]'s writers work on its inline `[u8; N]` storage and can't be shared as
+/// `const fn`s over a storage trait, so they are reached through
+/// [`write_with`](Self::write_with), which runs them on a scratch `Buffer` and copies
+/// the result in.
+pub struct SliceBuffer<'a> {
+    len: usize,
+    bytes: &'a mut [u8],
+}
+
+macro_rules! write_int {
+    ($($ty:ident $writefun:ident $max_len:literal)*) => {$(
+        #[doc = concat!("Writes `value` in decimal, like [`Buffer::", stringify!($writefun), "`].")]
+        pub const fn $writefun(&mut self, value: $ty) -> Result<(), BufferWriteFailed> {
+            let mut digits = Buffer::new::<$max_len>();
+            tri!(digits.$writefun(value));
+            self.push_str(digits.as_str())
+        }
+    )*};
+}
+
+impl<'a> SliceBuffer<'a> {
+    /// Creates an empty buffer that writes into `bytes`.
+    pub const fn new(bytes: &'a mut [u8]) -> Self {
+        Self { len: 0, bytes }
+    }
+
+    /// Removes everything written so far.
+    pub const fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns the written content.
+    pub const fn as_str(&self) -> &str {
+        let (written, _) = self.bytes.split_at(self.len);
+        unsafe { core::str::from_utf8_unchecked(written) }
+    }
+
+    /// Returns the written content mutably, see [`Buffer::as_mut_str`].
+    pub const fn as_mut_str(&mut self) -> &mut str {
+        let (written, _) = self.bytes.split_at_mut(self.len);
+        unsafe { core::str::from_utf8_unchecked_mut(written) }
+    }
+
+    /// Returns the length of the borrowed slice.
+    pub const fn capacity(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns how many bytes have been written.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether nothing has been written.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns how many more bytes fit.
+    pub const fn remaining_capacity(&self) -> usize {
+        self.bytes.len() - self.len
+    }
+
+    /// Writes `s`, or fails without writing anything if it doesn't fit.
+    pub const fn push_str(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        if s.len() > self.remaining_capacity() {
+            return Err(BufferWriteFailed);
+        }
+
+        let (_, spare) = self.bytes.split_at_mut(self.len);
+        let (dest, _) = spare.split_at_mut(s.len());
+        dest.copy_from_slice(s.as_bytes());
+        self.len += s.len();
+
+        Ok(())
+    }
+
+    /// Writes `value` as UTF-8.
+    pub const fn write_char(&mut self, value: char) -> Result<(), BufferWriteFailed> {
+        self.push_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    write_int! {
+        u8 write_u8 3
+        u16 write_u16 5
+        u32 write_u32 10
+        u64 write_u64 20
+        u128 write_u128 39
+        usize write_usize 20
+        i8 write_i8 4
+        i16 write_i16 6
+        i32 write_i32 11
+        i64 write_i64 20
+        i128 write_i128 40
+        isize write_isize 20
+    }
+
+    /// Runs `write` on an empty scratch [`Buffer`] of `N` bytes and copies what it wrote
+    /// into this buffer, so `write_with::<32>(|b| b.write_f64(x, 3))` writes a float.
+    ///
+    /// Fails without writing anything if `write` fails or its output doesn't fit.
+    pub fn write_with<const N: usize>(
+        &mut self,
+        write: impl FnOnce(&mut Buffer<[u8; N]>) -> Result<(), BufferWriteFailed>,
+    ) -> Result<(), BufferWriteFailed> {
+        let mut scratch = Buffer::new::<N>();
+        write(&mut scratch)?;
+        self.push_str(scratch.as_str())
+    }
+}
+
+#[test]
+fn test_slice_buffer() {
+    let mut storage = vec![0xff; 16];
+    let mut buffer = SliceBuffer::new(&mut storage);
+    assert_eq!(buffer.capacity(), 16);
+
+    buffer.push_str("x=").unwrap();
+    buffer.write_i32(-42).unwrap();
+    buffer.write_char('é').unwrap();
+    buffer.write_u64(u64::MAX).unwrap_err();
+    assert_eq!(buffer.as_str(), "x=-42é");
+
+    buffer.write_i128(i128::MIN).unwrap_err();
+    buffer.write_u8(255).unwrap();
+    assert_eq!(buffer.as_str(), "x=-42é255");
+    assert_eq!(buffer.remaining_capacity(), 6);
+
+    buffer.clear();
+    assert!(buffer.is_empty());
+    buffer.write_u128(1234567890123456).unwrap();
+    assert_eq!(buffer.as_str(), "1234567890123456");
+}
+
+#[test]
+fn test_slice_buffer_write_with() {
+    let mut storage = [0; 12];
+    let mut buffer = SliceBuffer::new(&mut storage);
+
+    buffer
+        .write_with::<16>(|b| b.write_hex_color([0xbe, 0xef, 0x01]))
+        .unwrap();
+    buffer.write_with::<16>(|b| b.write_f64(1.5, 1)).unwrap();
+    assert_eq!(buffer.as_str(), "#beef011.5");
+
+    assert!(buffer.write_with::<16>(|b| b.push_str("abc")).is_err());
+    assert!(buffer.write_with::<1>(|b| b.push_str("ab")).is_err());
+    assert_eq!(buffer.as_str(), "#beef011.5");
+}
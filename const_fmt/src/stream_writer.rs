@@ -0,0 +1,145 @@
+use crate::{Buffer, BufferWriteFailed, ByteBuffer};
+
+/// A writer that hands its content to a `flush` callback whenever its [`Buffer`] fills up,
+/// so a small buffer can produce output of any length.
+///
+/// Whatever is left is flushed when the writer is dropped.
+pub struct StreamWriter<B: ByteBuffer> {
+    buffer: Buffer<B>,
+    flush: fn(&str),
+}
+
+macro_rules! write_int {
+    ($($ty:ident $writefun:ident)*) => {$(
+        pub fn $writefun(&mut self, value: $ty) -> Result<(), BufferWriteFailed> {
+            self.write(|buffer| buffer.$writefun(value))
+        }
+    )*};
+}
+
+impl<B: ByteBuffer> StreamWriter<B> {
+    pub const fn new(flush: fn(&str)) -> Self {
+        Self {
+            buffer: Buffer::create(),
+            flush,
+        }
+    }
+
+    /// The content that hasn't been flushed yet.
+    pub const fn as_str(&self) -> &str {
+        self.buffer.as_str()
+    }
+
+    /// Passes the unflushed content to the callback and empties the buffer.
+    ///
+    /// Nothing happens if there is no unflushed content.
+    pub fn flush(&mut self) {
+        if !self.buffer.is_empty() {
+            (self.flush)(self.buffer.as_str());
+            self.buffer.clear();
+        }
+    }
+
+    /// Runs `write` on the buffer, flushing and trying again once if it fails.
+    ///
+    /// `write` must leave the buffer unchanged when it fails, like the `Buffer` writers do.
+    /// Fails if the output doesn't fit even in an empty buffer.
+    pub fn write(
+        &mut self,
+        mut write: impl FnMut(&mut Buffer<B>) -> Result<(), BufferWriteFailed>,
+    ) -> Result<(), BufferWriteFailed> {
+        if write(&mut self.buffer).is_ok() {
+            return Ok(());
+        }
+
+        self.flush();
+        write(&mut self.buffer)
+    }
+
+    /// Writes `s`, flushing as often as needed.
+    ///
+    /// `s` is split on char boundaries, so this only fails if a single character doesn't fit
+    /// in an empty buffer, and then everything before that character has been written.
+    pub fn push_str(&mut self, s: &str) -> Result<(), BufferWriteFailed> {
+        let mut rest = s;
+
+        loop {
+            let (written, done) = self.buffer.try_push_str(rest);
+
+            if done {
+                return Ok(());
+            }
+
+            if written == 0 && self.buffer.is_empty() {
+                return Err(BufferWriteFailed);
+            }
+
+            rest = rest.split_at(written).1;
+            self.flush();
+        }
+    }
+
+    pub fn write_char(&mut self, value: char) -> Result<(), BufferWriteFailed> {
+        self.write(|buffer| buffer.write_char(value))
+    }
+
+    write_int! {
+        u8 write_u8
+        u16 write_u16
+        u32 write_u32
+        u64 write_u64
+        u128 write_u128
+        usize write_usize
+        i8 write_i8
+        i16 write_i16
+        i32 write_i32
+        i64 write_i64
+        i128 write_i128
+        isize write_isize
+    }
+}
+
+impl<B: ByteBuffer> core::fmt::Write for StreamWriter<B> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s)
+            .map_err(|BufferWriteFailed| core::fmt::Error)
+    }
+}
+
+impl<B: ByteBuffer> Drop for StreamWriter<B> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[test]
+fn test_stream_writer() {
+    use core::fmt::Write;
+    use std::sync::Mutex;
+
+    static OUTPUT: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    fn flush(s: &str) {
+        OUTPUT.lock().unwrap().push(s.to_string());
+    }
+
+    let mut writer = StreamWriter::<[u8; 8]>::new(flush);
+    writer.push_str("hello, ").unwrap();
+    writer.write_u32(123_456).unwrap();
+    writer.push_str(" and a longer tail é").unwrap();
+    assert_eq!(writer.as_str(), " é");
+    write!(writer, "{}!", -1).unwrap();
+    drop(writer);
+
+    let output = core::mem::take(&mut *OUTPUT.lock().unwrap());
+    assert_eq!(output.concat(), "hello, 123456 and a longer tail é-1!");
+    assert!(output.iter().all(|chunk| chunk.len() <= 8));
+    assert_eq!(output[..2], ["hello, ", "123456 a"]);
+
+    // a character that can't fit even in an empty buffer
+    let mut writer = StreamWriter::<[u8; 1]>::new(flush);
+    assert!(writer.push_str("aé").is_err());
+    assert!(writer.write_u8(10).is_err());
+    drop(writer);
+    assert_eq!(OUTPUT.lock().unwrap().concat(), "a");
+}
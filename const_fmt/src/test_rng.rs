@@ -0,0 +1,12 @@
+// a small xorshift64 generator, so tests can cover lots of inputs reproducibly
+// `seed` must not be zero
+pub(crate) fn random_u64s(seed: u64) -> impl Iterator<Item = u64> {
+    let mut state = seed;
+
+    core::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    })
+}
@@ -0,0 +1,103 @@
+/// Returns whether `bytes` is valid UTF-8, in a `const` context.
+pub const fn is_valid_utf8(bytes: &[u8]) -> bool {
+    let mut i = 0;
+
+    while i < bytes.len() {
+        // the allowed range of the second byte, which rules out overlong encodings,
+        // surrogates and code points past U+10FFFF
+        let (len, lo, hi) = match bytes[i] {
+            0x00..=0x7f => {
+                i += 1;
+                continue;
+            }
+            0xc2..=0xdf => (2, 0x80, 0xbf),
+            0xe0 => (3, 0xa0, 0xbf),
+            0xe1..=0xec | 0xee..=0xef => (3, 0x80, 0xbf),
+            0xed => (3, 0x80, 0x9f),
+            0xf0 => (4, 0x90, 0xbf),
+            0xf1..=0xf3 => (4, 0x80, 0xbf),
+            0xf4 => (4, 0x80, 0x8f),
+            _ => return false,
+        };
+
+        if len > bytes.len() - i || bytes[i + 1] < lo || bytes[i + 1] > hi {
+            return false;
+        }
+
+        let mut j = 2;
+
+        while j < len {
+            if bytes[i + j] & 0b1100_0000 != 0b1000_0000 {
+                return false;
+            }
+
+            j += 1;
+        }
+
+        i += len;
+    }
+
+    true
+}
+
+/// Converts `bytes` to a `str` if it's valid UTF-8, in a `const` context.
+pub const fn from_utf8(bytes: &[u8]) -> Option<&str> {
+    if is_valid_utf8(bytes) {
+        Some(unsafe { core::str::from_utf8_unchecked(bytes) })
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_short_inputs() {
+    assert!(is_valid_utf8(b""));
+
+    for a in 0..=255u8 {
+        assert_eq!(is_valid_utf8(&[a]), core::str::from_utf8(&[a]).is_ok());
+
+        for b in 0..=255u8 {
+            let bytes = [a, b];
+            assert_eq!(is_valid_utf8(&bytes), core::str::from_utf8(&bytes).is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_random_inputs() {
+    let mut random = crate::test_rng::random_u64s(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..200_000 {
+        let bytes = random.next().unwrap().to_le_bytes();
+        // bias towards bytes that start multi-byte characters
+        let bytes = bytes.map(|byte| if byte & 1 == 0 { byte | 0x80 } else { byte });
+        let len = (random.next().unwrap() % 9) as usize;
+
+        let bytes = &bytes[..len];
+        assert_eq!(is_valid_utf8(bytes), core::str::from_utf8(bytes).is_ok());
+    }
+}
+
+#[test]
+fn test_from_utf8() {
+    for s in ["héllo", "日本語", "🦀 and ✓", "\u{10ffff}\u{d7ff}\u{e000}"] {
+        assert_eq!(from_utf8(s.as_bytes()), Some(s));
+    }
+
+    assert_eq!(from_utf8(b"\xed\xa0\x80"), None);
+    assert_eq!(from_utf8(b"\xf4\x90\x80\x80"), None);
+    assert_eq!(from_utf8(b"\xc0\xaf"), None);
+
+    const { assert!(is_valid_utf8("✓".as_bytes()) && !is_valid_utf8(b"\xe2\x9c")) };
+}
+
+#[cfg(kani)]
+#[kani::proof]
+#[kani::unwind(6)]
+fn prove_matches_core() {
+    let bytes: [u8; 4] = kani::any();
+    let len: usize = kani::any_where(|&len| len <= 4);
+    let bytes = &bytes[..len];
+
+    assert_eq!(is_valid_utf8(bytes), core::str::from_utf8(bytes).is_ok());
+}
@@ -0,0 +1,265 @@
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+
+/// Derives `ConstFormat`, writing the value like `Debug` would: `Name { field: value }`,
+/// `Name(value)`, or just `Name`. Enums write the variant name and its fields.
+///
+/// Every field must be `ConstFormat`, and generic types aren't supported yet.
+#[proc_macro_derive(ConstFormat)]
+pub fn derive_const_format(input: TokenStream) -> TokenStream {
+    let output = match derive(input) {
+        Ok(output) => output,
+        Err(message) => format!("::core::compile_error!({message:?});"),
+    };
+
+    output.parse().unwrap()
+}
+
+enum Fields {
+    Named(Vec<String>),
+    Unnamed(usize),
+    Unit,
+}
+
+enum Piece {
+    Str(String),
+    Field(usize),
+}
+
+type Tokens = core::iter::Peekable<proc_macro::token_stream::IntoIter>;
+
+fn derive(input: TokenStream) -> Result<String, String> {
+    let mut tokens = input.into_iter().peekable();
+    skip_attributes(&mut tokens);
+    skip_visibility(&mut tokens);
+
+    let kind = expect_ident(&mut tokens)?;
+    let name = expect_ident(&mut tokens)?;
+
+    if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '<') {
+        return Err(format!(
+            "`ConstFormat` can't be derived for `{name}` because generic types aren't supported"
+        ));
+    }
+
+    let arms = match kind.as_str() {
+        "struct" => {
+            let fields = match tokens.next() {
+                Some(TokenTree::Group(group)) => parse_fields(group.delimiter(), group.stream())?,
+                _ => Fields::Unit,
+            };
+
+            vec![arms("Self", &name, &fields)]
+        }
+        "enum" => {
+            let Some(TokenTree::Group(group)) = tokens.next() else {
+                return Err(format!("expected a body for enum `{name}`"));
+            };
+
+            let mut arms = Vec::new();
+
+            for variant in split_commas(group.stream()) {
+                let mut tokens = TokenStream::from_iter(variant).into_iter().peekable();
+                skip_attributes(&mut tokens);
+
+                let variant = expect_ident(&mut tokens)?;
+                let fields = match tokens.next() {
+                    Some(TokenTree::Group(group)) => {
+                        parse_fields(group.delimiter(), group.stream())?
+                    }
+                    _ => Fields::Unit,
+                };
+
+                arms.push(self::arms(&format!("Self::{variant}"), &variant, &fields));
+            }
+
+            arms
+        }
+        _ => return Err("`ConstFormat` can only be derived for structs and enums".to_string()),
+    };
+
+    let (display_arms, max_len_arms): (Vec<_>, Vec<_>) = arms.into_iter().unzip();
+
+    let (display_body, max_len_body) = if display_arms.is_empty() {
+        ("match *self {}".to_string(), "match *self {}".to_string())
+    } else {
+        (
+            format!("match self {{ {} }}", display_arms.concat()),
+            format!("match self {{ {} }}", max_len_arms.concat()),
+        )
+    };
+
+    Ok(format!(
+        "#[automatically_derived]
+        impl ::const_fmt::macros::ConstFormat for {name} {{
+            type Writer = ::const_fmt::macros::DerivedWriter<Self>;
+
+            fn write_to<B: ::const_fmt::ByteBuffer>(
+                &self,
+                buffer: &mut ::const_fmt::Buffer<B>,
+            ) -> ::core::result::Result<(), ::const_fmt::BufferWriteFailed> {{
+                ::const_fmt::macros::DerivedDisplay::display(self, buffer)
+            }}
+
+            fn max_len(&self) -> usize {{
+                ::const_fmt::macros::DerivedDisplay::max_len(self)
+            }}
+        }}
+
+        #[automatically_derived]
+        impl ::const_fmt::macros::DerivedDisplay for {name} {{
+            fn display<B: ::const_fmt::ByteBuffer>(
+                &self,
+                buffer: &mut ::const_fmt::Buffer<B>,
+            ) -> ::core::result::Result<(), ::const_fmt::BufferWriteFailed> {{
+                {display_body}
+            }}
+
+            fn max_len(&self) -> usize {{
+                {max_len_body}
+            }}
+        }}"
+    ))
+}
+
+// the match arms for `display` and `max_len`, in that order
+fn arms(path: &str, name: &str, fields: &Fields) -> (String, String) {
+    let mut pieces = vec![Piece::Str(name.to_string())];
+
+    let bindings = match fields {
+        Fields::Named(names) if !names.is_empty() => {
+            for (i, field) in names.iter().enumerate() {
+                let sep = if i == 0 { " { " } else { ", " };
+                pieces.push(Piece::Str(format!("{sep}{}: ", unraw(field))));
+                pieces.push(Piece::Field(i));
+            }
+
+            pieces.push(Piece::Str(" }".to_string()));
+
+            let bindings: Vec<_> = names
+                .iter()
+                .enumerate()
+                .map(|(i, field)| format!("{field}: __field_{i}"))
+                .collect();
+            format!("{{ {} }}", bindings.join(", "))
+        }
+        Fields::Unnamed(count) if *count != 0 => {
+            for i in 0..*count {
+                pieces.push(Piece::Str(if i == 0 { "(" } else { ", " }.to_string()));
+                pieces.push(Piece::Field(i));
+            }
+
+            pieces.push(Piece::Str(")".to_string()));
+
+            let bindings: Vec<_> = (0..*count).map(|i| format!("{i}: __field_{i}")).collect();
+            format!("{{ {} }}", bindings.join(", "))
+        }
+        _ => "{ .. }".to_string(),
+    };
+
+    let mut body = String::new();
+    let mut pending = String::new();
+    let mut len = 0;
+    let mut len_body = String::new();
+
+    for piece in pieces {
+        match piece {
+            Piece::Str(s) => {
+                len += s.len();
+                pending.push_str(&s);
+            }
+            Piece::Field(i) => {
+                let writer = format!("::const_fmt::get_writer!(*__field_{i})");
+                body += &format!("buffer.push_str({:?})?;", core::mem::take(&mut pending));
+                body += &format!("{writer}.display(__field_{i}, buffer)?;");
+                len_body += &format!(".saturating_add({writer}.max_len(__field_{i}))");
+            }
+        }
+    }
+
+    body += &format!("buffer.push_str({pending:?})");
+
+    (
+        format!("{path} {bindings} => {{ {body} }}"),
+        format!("{path} {bindings} => {len}usize{len_body},"),
+    )
+}
+
+fn parse_fields(delimiter: Delimiter, stream: TokenStream) -> Result<Fields, String> {
+    match delimiter {
+        Delimiter::Brace => {
+            let mut names = Vec::new();
+
+            for field in split_commas(stream) {
+                let mut tokens = TokenStream::from_iter(field).into_iter().peekable();
+                skip_attributes(&mut tokens);
+                skip_visibility(&mut tokens);
+                names.push(expect_ident(&mut tokens)?);
+            }
+
+            Ok(Fields::Named(names))
+        }
+        Delimiter::Parenthesis => Ok(Fields::Unnamed(split_commas(stream).len())),
+        _ => Ok(Fields::Unit),
+    }
+}
+
+// splits on commas that aren't nested inside of `<...>`, dropping empty segments
+fn split_commas(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut segments = vec![Vec::new()];
+    let mut depth = 0usize;
+    let mut after_joint_dash = false;
+
+    for token in stream {
+        let mut is_joint_dash = false;
+
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                ',' if depth == 0 => {
+                    segments.push(Vec::new());
+                    continue;
+                }
+                '<' => depth += 1,
+                // `->` isn't a closing angle bracket
+                '>' if !after_joint_dash => depth = depth.saturating_sub(1),
+                '-' => is_joint_dash = punct.spacing() == Spacing::Joint,
+                _ => (),
+            }
+        }
+
+        after_joint_dash = is_joint_dash;
+        segments.last_mut().unwrap().push(token);
+    }
+
+    segments.retain(|segment| !segment.is_empty());
+    segments
+}
+
+fn skip_attributes(tokens: &mut Tokens) {
+    while matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '#') {
+        tokens.next();
+        tokens.next();
+    }
+}
+
+fn skip_visibility(tokens: &mut Tokens) {
+    if matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+        tokens.next();
+
+        if matches!(tokens.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+        {
+            tokens.next();
+        }
+    }
+}
+
+fn expect_ident(tokens: &mut Tokens) -> Result<String, String> {
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) => Ok(ident.to_string()),
+        Some(token) => Err(format!("expected an identifier, found `{token}`")),
+        None => Err("expected an identifier".to_string()),
+    }
+}
+
+fn unraw(ident: &str) -> &str {
+    ident.strip_prefix("r#").unwrap_or(ident)
+}
@@ -0,0 +1,108 @@
+use const_fmt::{Buffer, get_writer};
+use const_fmt_derive::ConstFormat;
+
+#[derive(ConstFormat)]
+struct Point {
+    x: i32,
+    pub r#y: u8,
+}
+
+#[derive(ConstFormat)]
+struct Pair(u16, &'static str);
+
+#[derive(ConstFormat)]
+struct Unit;
+
+#[derive(ConstFormat)]
+struct Nested {
+    point: Point,
+    pair: Pair,
+    unit: Unit,
+}
+
+#[derive(ConstFormat)]
+struct Borrowed {
+    value: &'static u32,
+}
+
+#[derive(ConstFormat)]
+enum Shape {
+    Empty,
+    Circle(u32),
+    Rect { width: u32, height: u32 },
+}
+
+#[test]
+fn test_derive_struct() {
+    let value = Nested {
+        point: Point { x: -1, y: 2 },
+        pair: Pair(3, "four"),
+        unit: Unit,
+    };
+
+    let mut buffer = Buffer::new::<96>();
+    get_writer!(value).display(&value, &mut buffer).unwrap();
+
+    assert_eq!(
+        buffer.as_str(),
+        "Nested { point: Point { x: -1, y: 2 }, pair: Pair(3, four), unit: Unit }"
+    );
+}
+
+#[test]
+fn test_derive_enum() {
+    for (shape, expected) in [
+        (Shape::Empty, "Empty"),
+        (Shape::Circle(5), "Circle(5)"),
+        (
+            Shape::Rect {
+                width: 3,
+                height: 4,
+            },
+            "Rect { width: 3, height: 4 }",
+        ),
+    ] {
+        let mut buffer = Buffer::new::<32>();
+        get_writer!(shape).display(&shape, &mut buffer).unwrap();
+        assert_eq!(buffer.as_str(), expected);
+    }
+}
+
+#[test]
+fn test_derive_borrowed_field() {
+    let value = Borrowed { value: &7 };
+
+    let mut buffer = Buffer::new::<32>();
+    get_writer!(value).display(&value, &mut buffer).unwrap();
+    assert_eq!(buffer.as_str(), "Borrowed { value: 7 }");
+}
+
+#[test]
+fn test_derive_array() {
+    let shapes = [Shape::Empty, Shape::Circle(2)];
+
+    let mut buffer = Buffer::new::<32>();
+    buffer.write_array(&shapes).unwrap();
+    assert_eq!(buffer.as_str(), "[Empty, Circle(2)]");
+}
+
+#[test]
+fn test_derive_max_len() {
+    let value = Nested {
+        point: Point { x: -1, y: 2 },
+        pair: Pair(3, "four"),
+        unit: Unit,
+    };
+
+    let mut buffer = Buffer::new::<128>();
+    get_writer!(value).display(&value, &mut buffer).unwrap();
+    assert!(buffer.len() <= get_writer!(value).max_len(&value));
+
+    assert_eq!(get_writer!(Unit).max_len(&Unit), 4);
+    assert_eq!(get_writer!(Shape::Empty).max_len(&Shape::Empty), 5);
+    let circle = Shape::Circle(1);
+    assert_eq!(
+        get_writer!(circle).max_len(&circle),
+        "Circle()".len() + u32::MAX.to_string().len()
+    );
+}